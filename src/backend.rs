@@ -0,0 +1,381 @@
+//! Blocking machinery behind [`WaitEvent`](crate::WaitEvent).
+//!
+//! A backend owns the state value, and provides locking, waiting with a deadline, and notification. The default is
+//! [`StdBackend`] (`std::sync::Mutex` + `Condvar`). With the `parking_lot` feature, [`ParkingLotBackend`] is also
+//! available, and with the `futex` feature on Linux, [`FutexBackend`] waits on raw futexes. Other primitives can be
+//! plugged in by implementing [`EventBackend`] and selecting it per event with `WaitEvent<T, MyBackend<T>>`.
+//!
+//! With the `lock_api` feature, [`RawBackend`] builds a backend out of any `lock_api::RawMutex` plus a
+//! [`RawCondvar`], so only the raw primitives have to be provided. This is how an RTOS mutex and semaphore are
+//! plugged in; the crate itself ships no RTOS port.
+
+use std::{ ops::DerefMut, sync::{ Condvar, Mutex, MutexGuard }, time::Instant };
+#[cfg(all(feature = "futex", target_os = "linux"))]
+use std::sync::atomic::{ AtomicU32, Ordering };
+use crate::Result;
+
+// ------------------------------ DATA TYPES ----------------------------------
+/// Lock + condition variable pair which owns a state value of type `T`.
+pub trait EventBackend<T> {
+    type Guard<'a>: DerefMut<Target = T> where Self: 'a;
+
+    fn new(value: T) -> Self;
+
+    /// Consume the backend, returning the state value.
+    fn into_inner(self) -> T;
+
+    fn lock(&self) -> Result<Self::Guard<'_>>;
+
+    /// Block until notified, or until `deadline` passes if any. Returns the guard, and whether the wakeup came before
+    /// the deadline.
+    fn wait_until<'a>(&'a self, guard: Self::Guard<'a>, deadline: Option<Instant>) -> Result<(Self::Guard<'a>, bool)>;
+
+    fn notify_one(&self);
+
+    fn notify_all(&self);
+}
+
+/// Marker of backends which can never be poisoned, enabling the infallible API of
+/// [`WaitEvent`](crate::WaitEvent) (e.g. [`wait_infallible`](crate::WaitEvent::wait_infallible)).
+pub trait NonPoisoning {}
+
+/// Backend based on `std::sync::Mutex` and `Condvar`.
+pub struct StdBackend<T> {
+    lock: Mutex<T>,
+    cond: Condvar
+}
+
+/// Backend based on `parking_lot::Mutex` and `Condvar`, which never poisons. Only available with the `parking_lot`
+/// feature.
+///
+/// # Examples
+///
+/// ```rust
+/// # use std::{thread, time::Duration};
+/// use sync_wait_object::{WaitEvent, ParkingLotBackend};
+///
+/// let ev = WaitEvent::<_, ParkingLotBackend<_>>::with_backend(0);
+/// let mut setter = ev.clone();
+///
+/// thread::spawn(move || setter.set_state(3).unwrap());
+/// assert_eq!(*ev.wait(Some(Duration::from_secs(1)), |v| *v == 3).unwrap(), 3);
+/// ```
+#[cfg(feature = "parking_lot")]
+pub struct ParkingLotBackend<T> {
+    lock: parking_lot::Mutex<T>,
+    cond: parking_lot::Condvar
+}
+
+/// Raw condition variable, used together with a `lock_api::RawMutex` by [`RawBackend`]. Only available with the
+/// `lock_api` feature.
+#[cfg(feature = "lock_api")]
+pub trait RawCondvar {
+    /// Initial value of the condition variable.
+    const INIT: Self;
+
+    /// Unlock `mutex`, block until notified or until `deadline` passes if any, then lock `mutex` again. Returns whether
+    /// the wakeup came before the deadline. Unlocking and starting to wait must be atomic with regard to notifications.
+    ///
+    /// # Safety
+    ///
+    /// `mutex` must be locked by the current thread.
+    unsafe fn wait<M: lock_api::RawMutex>(&self, mutex: &M, deadline: Option<Instant>) -> bool;
+
+    fn notify_one(&self);
+
+    fn notify_all(&self);
+}
+
+/// Portable [`RawCondvar`] on top of `std::sync::Condvar`, for raw mutexes without a native condition variable. Only
+/// available with the `lock_api` feature.
+#[cfg(feature = "lock_api")]
+pub struct ParkCondvar {
+    generation: Mutex<u64>,
+    cond: Condvar
+}
+
+/// Backend made of a `lock_api` raw mutex `M` and a raw condition variable `C`, which never poisons. Only available
+/// with the `lock_api` feature.
+///
+/// # Examples
+///
+/// A spin lock standing in for an RTOS mutex:
+///
+/// ```rust
+/// # use std::{thread, time::Duration, sync::atomic::{AtomicBool, Ordering}};
+/// use sync_wait_object::{WaitEvent, RawBackend};
+///
+/// struct SpinLock(AtomicBool);
+///
+/// unsafe impl lock_api::RawMutex for SpinLock {
+///     const INIT: SpinLock = SpinLock(AtomicBool::new(false));
+///     type GuardMarker = lock_api::GuardSend;
+///
+///     fn lock(&self) { while !self.try_lock() { std::hint::spin_loop() } }
+///     fn try_lock(&self) -> bool {
+///         self.0.compare_exchange(false, true, Ordering::Acquire, Ordering::Relaxed).is_ok()
+///     }
+///     unsafe fn unlock(&self) { self.0.store(false, Ordering::Release) }
+/// }
+///
+/// let ev = WaitEvent::<_, RawBackend<_, SpinLock>>::with_backend(0);
+/// let mut setter = ev.clone();
+///
+/// thread::spawn(move || setter.set_state(3).unwrap());
+/// assert_eq!(*ev.wait(Some(Duration::from_secs(1)), |v| *v == 3).unwrap(), 3);
+/// ```
+#[cfg(feature = "lock_api")]
+pub struct RawBackend<T, M: lock_api::RawMutex, C: RawCondvar = ParkCondvar> {
+    lock: lock_api::Mutex<M, T>,
+    cond: C
+}
+
+/// `lock_api` raw mutex on a Linux futex: uncontended locking and unlocking take no system call. Only available with
+/// the `futex` feature on Linux.
+#[cfg(all(feature = "futex", target_os = "linux"))]
+pub struct FutexMutex {
+    /// 0 when unlocked, 1 when locked, 2 when locked with possible waiters
+    state: AtomicU32
+}
+
+/// [`RawCondvar`] on a Linux futex holding a notification sequence number. Only available with the `futex` feature on
+/// Linux.
+#[cfg(all(feature = "futex", target_os = "linux"))]
+pub struct FutexCondvar {
+    sequence: AtomicU32
+}
+
+/// Backend made of a [`FutexMutex`] and a [`FutexCondvar`], which never poisons. Only available with the `futex`
+/// feature on Linux.
+///
+/// # Examples
+///
+/// ```rust
+/// # use std::{thread, time::Duration};
+/// use sync_wait_object::{WaitEvent, FutexBackend, WaitObjectError};
+///
+/// let ev = WaitEvent::<_, FutexBackend<_>>::with_backend(0);
+/// let mut setter = ev.clone();
+///
+/// thread::spawn(move || setter.set_state(3).unwrap());
+/// assert_eq!(*ev.wait(Some(Duration::from_secs(1)), |v| *v == 3).unwrap(), 3);
+/// assert_eq!(ev.wait(Some(Duration::from_millis(10)), |v| *v == 4).err(), Some(WaitObjectError::Timeout));
+/// ```
+#[cfg(all(feature = "futex", target_os = "linux"))]
+pub type FutexBackend<T> = RawBackend<T, FutexMutex, FutexCondvar>;
+
+// ------------------------------ FUNCTIONS -------------------------------------
+/// Block while `futex` holds `expected`, until woken up or `deadline` passes if any. Returns `false` on timeout only.
+#[cfg(all(feature = "futex", target_os = "linux"))]
+fn futex_wait(futex: &AtomicU32, expected: u32, deadline: Option<Instant>) -> bool {
+    let timeout = match deadline {
+        Some(d) => {
+            let remaining = d.saturating_duration_since(Instant::now());
+            if remaining.is_zero() { return false }
+            Some(libc::timespec {
+                tv_sec: remaining.as_secs().min(libc::time_t::MAX as u64) as libc::time_t,
+                tv_nsec: remaining.subsec_nanos() as libc::c_long
+            })
+        },
+        None => None
+    };
+    let timeout_ptr = timeout.as_ref().map_or(std::ptr::null(), |t| t as *const libc::timespec);
+    // SAFETY: `futex` is a valid aligned 32-bit atomic, and `timeout_ptr` is null or points to a live timespec
+    let r = unsafe {
+        libc::syscall(libc::SYS_futex, futex.as_ptr(), libc::FUTEX_WAIT | libc::FUTEX_PRIVATE_FLAG, expected, timeout_ptr)
+    };
+    // a changed value (EAGAIN) or an interruption (EINTR) counts as a wake-up, the caller re-checks its condition
+    r == 0 || std::io::Error::last_os_error().raw_os_error() != Some(libc::ETIMEDOUT)
+}
+
+/// Wake up to `count` threads blocked on `futex`.
+#[cfg(all(feature = "futex", target_os = "linux"))]
+fn futex_wake(futex: &AtomicU32, count: i32) {
+    // SAFETY: `futex` is a valid aligned 32-bit atomic
+    unsafe { libc::syscall(libc::SYS_futex, futex.as_ptr(), libc::FUTEX_WAKE | libc::FUTEX_PRIVATE_FLAG, count) };
+}
+
+// ------------------------------ IMPLEMENTATIONS ------------------------------
+impl<T> EventBackend<T> for StdBackend<T> {
+    type Guard<'a> = MutexGuard<'a, T> where Self: 'a;
+
+    #[inline]
+    fn new(value: T) -> Self {
+        Self { lock: Mutex::new(value), cond: Condvar::new() }
+    }
+
+    #[inline]
+    fn into_inner(self) -> T {
+        self.lock.into_inner().unwrap_or_else(|e| e.into_inner())
+    }
+
+    #[inline]
+    fn lock(&self) -> Result<Self::Guard<'_>> {
+        Ok(self.lock.lock()?)
+    }
+
+    fn wait_until<'a>(&'a self, guard: Self::Guard<'a>, deadline: Option<Instant>) -> Result<(Self::Guard<'a>, bool)> {
+        Ok(match deadline {
+            Some(d) => {
+                let (g, r) = self.cond.wait_timeout(guard, d.saturating_duration_since(Instant::now()))?;
+                (g, !r.timed_out())
+            },
+            None => (self.cond.wait(guard)?, true)
+        })
+    }
+
+    #[inline] fn notify_one(&self) { self.cond.notify_one() }
+    #[inline] fn notify_all(&self) { self.cond.notify_all() }
+}
+
+#[cfg(feature = "parking_lot")]
+impl<T> NonPoisoning for ParkingLotBackend<T> {}
+
+#[cfg(feature = "parking_lot")]
+impl<T> EventBackend<T> for ParkingLotBackend<T> {
+    type Guard<'a> = parking_lot::MutexGuard<'a, T> where Self: 'a;
+
+    #[inline]
+    fn new(value: T) -> Self {
+        Self { lock: parking_lot::Mutex::new(value), cond: parking_lot::Condvar::new() }
+    }
+
+    #[inline]
+    fn into_inner(self) -> T { self.lock.into_inner() }
+
+    #[inline]
+    fn lock(&self) -> Result<Self::Guard<'_>> {
+        Ok(self.lock.lock())
+    }
+
+    fn wait_until<'a>(&'a self, mut guard: Self::Guard<'a>, deadline: Option<Instant>) -> Result<(Self::Guard<'a>, bool)> {
+        let notified = match deadline {
+            Some(d) => !self.cond.wait_until(&mut guard, d).timed_out(),
+            None => { self.cond.wait(&mut guard); true }
+        };
+        Ok((guard, notified))
+    }
+
+    #[inline] fn notify_one(&self) { self.cond.notify_one(); }
+    #[inline] fn notify_all(&self) { self.cond.notify_all(); }
+}
+
+#[cfg(feature = "lock_api")]
+impl RawCondvar for ParkCondvar {
+    const INIT: Self = Self { generation: Mutex::new(0), cond: Condvar::new() };
+
+    unsafe fn wait<M: lock_api::RawMutex>(&self, mutex: &M, deadline: Option<Instant>) -> bool {
+        // the generation is taken before unlocking `mutex`, so a notification in between is not lost
+        let generation = self.generation.lock().unwrap_or_else(|e| e.into_inner());
+        let start = *generation;
+        mutex.unlock();
+        let notified = match deadline {
+            Some(d) => {
+                let timeout = d.saturating_duration_since(Instant::now());
+                let (g, r) = self.cond.wait_timeout_while(generation, timeout, |g| *g == start).unwrap_or_else(|e| e.into_inner());
+                drop(g);
+                !r.timed_out()
+            },
+            None => { drop(self.cond.wait_while(generation, |g| *g == start).unwrap_or_else(|e| e.into_inner())); true }
+        };
+        mutex.lock();
+        notified
+    }
+
+    fn notify_one(&self) {
+        let mut generation = self.generation.lock().unwrap_or_else(|e| e.into_inner());
+        *generation = generation.wrapping_add(1);
+        self.cond.notify_one();
+    }
+
+    fn notify_all(&self) {
+        let mut generation = self.generation.lock().unwrap_or_else(|e| e.into_inner());
+        *generation = generation.wrapping_add(1);
+        self.cond.notify_all();
+    }
+}
+
+#[cfg(feature = "lock_api")]
+impl<T, M: lock_api::RawMutex, C: RawCondvar> NonPoisoning for RawBackend<T, M, C> {}
+
+#[cfg(feature = "lock_api")]
+impl<T, M: lock_api::RawMutex, C: RawCondvar> EventBackend<T> for RawBackend<T, M, C> {
+    type Guard<'a> = lock_api::MutexGuard<'a, M, T> where Self: 'a;
+
+    #[inline]
+    fn new(value: T) -> Self {
+        Self { lock: lock_api::Mutex::new(value), cond: C::INIT }
+    }
+
+    #[inline]
+    fn into_inner(self) -> T { self.lock.into_inner() }
+
+    #[inline]
+    fn lock(&self) -> Result<Self::Guard<'_>> {
+        Ok(self.lock.lock())
+    }
+
+    fn wait_until<'a>(&'a self, guard: Self::Guard<'a>, deadline: Option<Instant>) -> Result<(Self::Guard<'a>, bool)> {
+        // SAFETY: the guard proves the mutex is locked by this thread, and it is locked again when `wait` returns
+        let notified = unsafe { self.cond.wait(lock_api::MutexGuard::mutex(&guard).raw(), deadline) };
+        Ok((guard, notified))
+    }
+
+    #[inline] fn notify_one(&self) { self.cond.notify_one(); }
+    #[inline] fn notify_all(&self) { self.cond.notify_all(); }
+}
+
+#[cfg(all(feature = "futex", target_os = "linux"))]
+impl FutexMutex {
+    #[cold]
+    fn lock_contended(&self) {
+        while self.state.swap(2, Ordering::Acquire) != 0 {
+            futex_wait(&self.state, 2, None);
+        }
+    }
+}
+
+#[cfg(all(feature = "futex", target_os = "linux"))]
+unsafe impl lock_api::RawMutex for FutexMutex {
+    const INIT: Self = Self { state: AtomicU32::new(0) };
+    type GuardMarker = lock_api::GuardSend;
+
+    #[inline]
+    fn lock(&self) {
+        if !self.try_lock() { self.lock_contended() }
+    }
+
+    #[inline]
+    fn try_lock(&self) -> bool {
+        self.state.compare_exchange(0, 1, Ordering::Acquire, Ordering::Relaxed).is_ok()
+    }
+
+    #[inline]
+    unsafe fn unlock(&self) {
+        if self.state.swap(0, Ordering::Release) == 2 { futex_wake(&self.state, 1) }
+    }
+}
+
+#[cfg(all(feature = "futex", target_os = "linux"))]
+impl RawCondvar for FutexCondvar {
+    const INIT: Self = Self { sequence: AtomicU32::new(0) };
+
+    unsafe fn wait<M: lock_api::RawMutex>(&self, mutex: &M, deadline: Option<Instant>) -> bool {
+        // the sequence is read before unlocking `mutex`, so the futex does not block if a notification came in between
+        let sequence = self.sequence.load(Ordering::Relaxed);
+        mutex.unlock();
+        let notified = futex_wait(&self.sequence, sequence, deadline);
+        mutex.lock();
+        notified
+    }
+
+    fn notify_one(&self) {
+        self.sequence.fetch_add(1, Ordering::Relaxed);
+        futex_wake(&self.sequence, 1);
+    }
+
+    fn notify_all(&self) {
+        self.sequence.fetch_add(1, Ordering::Relaxed);
+        futex_wake(&self.sequence, i32::MAX);
+    }
+}
@@ -0,0 +1,57 @@
+use std::{ marker::PhantomData, sync::Arc, time::Duration };
+use crate::{ EventBackend, StdBackend, WaitEvent };
+
+// ------------------------------ DATA TYPES ----------------------------------
+/// Builder of [`WaitEvent`], from [`WaitEvent::builder`]. For another backend, start from
+/// `WaitEventBuilder::<_, MyBackend<_>>::default()`.
+///
+/// # Examples
+///
+/// ```rust
+/// # use std::time::Duration;
+/// use sync_wait_object::{WaitEvent, WaitObjectError};
+///
+/// let ev = WaitEvent::builder().default_timeout(Duration::from_millis(10)).build(0);
+/// assert_eq!(ev.default_timeout(), Some(Duration::from_millis(10)));
+///
+/// // `None` now means the default timeout instead of forever
+/// assert_eq!(ev.wait(None, |v| *v == 1).err(), Some(WaitObjectError::Timeout));
+/// ```
+pub struct WaitEventBuilder<T, B = StdBackend<T>> {
+    default_timeout: Option<Duration>,
+    name: Option<Arc<str>>,
+    _marker: PhantomData<fn() -> (T, B)>
+}
+
+// ------------------------------ IMPLEMENTATIONS ------------------------------
+impl<T> WaitEvent<T> {
+    #[inline]
+    pub fn builder() -> WaitEventBuilder<T> { WaitEventBuilder::default() }
+}
+
+impl<T, B: EventBackend<T>> WaitEventBuilder<T, B> {
+    /// Timeout of waits which are given `None`, overriding the one of [`WaitPolicy`](crate::WaitPolicy). Use
+    /// [`WaitEvent::wait_forever`] to wait without limit.
+    #[inline]
+    pub fn default_timeout(mut self, timeout: Duration) -> Self {
+        self.default_timeout = Some(timeout);
+        self
+    }
+
+    /// Name of the event, for diagnostics.
+    #[inline]
+    pub fn name(mut self, name: impl Into<Arc<str>>) -> Self {
+        self.name = Some(name.into());
+        self
+    }
+
+    pub fn build(self, initial_state: T) -> WaitEvent<T, B> {
+        WaitEvent::with_config(initial_state, self.default_timeout, self.name)
+    }
+}
+
+impl<T, B> Default for WaitEventBuilder<T, B> {
+    fn default() -> Self {
+        Self { default_timeout: None, name: None, _marker: PhantomData }
+    }
+}
@@ -0,0 +1,90 @@
+use std::{ cell::RefCell, sync::{ Arc, atomic::{ AtomicBool, Ordering } }, time::Duration };
+use crate::{ ManualResetEvent, SignalWaitable, Result };
+
+// ------------------------------ DATA TYPES ----------------------------------
+/// Cancellation signal shared by all its clones.
+///
+/// Besides being checked explicitly, a token can be installed for the current thread with [`scope`](Self::scope). Any
+/// wait inside the scope then fails with [`WaitObjectError::Cancelled`](crate::WaitObjectError::Cancelled) once the
+/// token is cancelled. Since the token cannot notify the events being waited on, such waits re-check the token every
+/// [`ANY_POLL_INTERVAL`](crate::ANY_POLL_INTERVAL).
+///
+/// # Examples
+///
+/// ```rust
+/// # use std::{thread, time::Duration};
+/// use sync_wait_object::{CancellationToken, WaitEvent, WaitObjectError};
+///
+/// let token = CancellationToken::new();
+/// let canceller = token.clone();
+/// let ev = WaitEvent::new_init(0);
+///
+/// thread::spawn(move || {
+///     thread::sleep(Duration::from_millis(10));
+///     canceller.cancel();
+/// });
+///
+/// let r = token.scope(|| ev.wait(None, |v| *v == 1).map(|_| ()));
+/// assert_eq!(r, Err(WaitObjectError::Cancelled));
+/// ```
+#[derive(Clone, Default)]
+pub struct CancellationToken {
+    cancelled: Arc<AtomicBool>,
+    event: ManualResetEvent
+}
+
+thread_local! {
+    static SCOPED: RefCell<Vec<CancellationToken>> = const { RefCell::new(Vec::new()) };
+}
+
+/// Removes the token of a scope when it ends, even by panic.
+struct ScopeGuard;
+
+// ------------------------------ IMPLEMENTATIONS ------------------------------
+impl CancellationToken {
+    #[inline]
+    pub fn new() -> Self { Self::default() }
+
+    /// Cancel the token. All clones observe it.
+    pub fn cancel(&self) {
+        self.cancelled.store(true, Ordering::SeqCst);
+        let _ = self.event.clone().set();
+    }
+
+    #[inline]
+    pub fn is_cancelled(&self) -> bool {
+        self.cancelled.load(Ordering::SeqCst)
+    }
+
+    /// Wait until the token is cancelled, or timed-out from `timeout`.
+    pub fn wait_cancelled(&self, timeout: Option<Duration>) -> Result<()> {
+        match timeout {
+            Some(t) => self.event.wait(t),
+            None => self.event.wait_until_set()
+        }
+    }
+
+    /// Run `f` with all waits on the current thread interruptible by this token. Scopes can be nested, in which case
+    /// any of the tokens cancels the waits.
+    pub fn scope<R>(&self, f: impl FnOnce() -> R) -> R {
+        SCOPED.with(|s| s.borrow_mut().push(self.clone()));
+        let _guard = ScopeGuard;
+        f()
+    }
+
+    /// Whether the current thread is inside any cancellation scope.
+    pub(crate) fn in_scope() -> bool {
+        SCOPED.with(|s| !s.borrow().is_empty())
+    }
+
+    /// Whether any token installed on the current thread is cancelled.
+    pub(crate) fn scope_cancelled() -> bool {
+        SCOPED.with(|s| s.borrow().iter().any(CancellationToken::is_cancelled))
+    }
+}
+
+impl Drop for ScopeGuard {
+    fn drop(&mut self) {
+        SCOPED.with(|s| s.borrow_mut().pop());
+    }
+}
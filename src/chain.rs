@@ -0,0 +1,126 @@
+use std::{ cell::{Cell, RefCell}, collections::VecDeque, sync::{ atomic::{AtomicU64, Ordering}, Arc, Mutex, Weak } };
+use crate::{ Inner, ManualResetEvent, StdBackend, SubscriptionGuard, WaitEvent, WaitObjectError, Result };
+
+// ------------------------------ DATA TYPES ----------------------------------
+/// Chain from [`ManualResetEvent::chain_to`]. Propagation stops when this guard drops.
+#[must_use = "propagation stops when the guard drops"]
+pub struct ChainGuard {
+    _subscription: SubscriptionGuard<bool>,
+    edge: Edge
+}
+
+type Node = Weak<Inner<bool, StdBackend<bool>>>;
+
+/// Chain from a source to a target. The weak references keep the addresses of both events reserved, so a freed
+/// event cannot be mistaken for a new one at the same address.
+type Edge = (Node, Node);
+
+/// Edges of all live chains, to detect cycles.
+static CHAINS: Mutex<Vec<Edge>> = Mutex::new(Vec::new());
+
+/// Propagation of a chain, shared by the listener and the propagations it defers.
+struct Link {
+    target: WaitEvent<bool>,
+    /// Sequence number of the latest change of the source, advanced under the source's lock.
+    queued: AtomicU64,
+    /// Sequence number of the latest change applied to the target.
+    applied: Mutex<u64>
+}
+
+thread_local! {
+    /// Propagations deferred by listeners of this thread until the source's lock is released.
+    static DEFERRED: RefCell<VecDeque<(Arc<Link>, u64, bool)>> = const { RefCell::new(VecDeque::new()) };
+    static PROPAGATING: Cell<bool> = const { Cell::new(false) };
+}
+
+/// Clears [`PROPAGATING`] when draining ends, even by panic.
+struct PropagatingGuard;
+
+// ------------------------------ FUNCTIONS -----------------------------------
+/// Apply the propagations deferred on this thread. Called once the lock of the changed event is released, so a chain
+/// never locks its target while the source is locked.
+pub(crate) fn propagate_deferred() {
+    // propagations deferred by the targets are applied by the outermost call, once the target's link is released
+    if PROPAGATING.with(|p| p.replace(true)) { return }
+    let _guard = PropagatingGuard;
+    while let Some((link, seq, state)) = DEFERRED.with(|d| d.borrow_mut().pop_front()) {
+        let mut applied = link.applied.lock().unwrap_or_else(|e| e.into_inner());
+        // another thread may already have applied a later change of the source
+        if seq > *applied {
+            *applied = seq;
+            let _ = link.target.modify(|t| *t = state);
+        }
+    }
+}
+
+fn reaches(chains: &[Edge], from: &Node, to: &Node) -> bool {
+    let mut pending = vec![from.clone()];
+    let mut visited: Vec<Node> = Vec::new();
+    while let Some(node) = pending.pop() {
+        if node.ptr_eq(to) { return true }
+        if visited.iter().any(|v| v.ptr_eq(&node)) { continue }
+        pending.extend(chains.iter().filter(|(s, _)| s.ptr_eq(&node)).map(|(_, t)| t.clone()));
+        visited.push(node);
+    }
+    false
+}
+
+// ------------------------------ IMPLEMENTATIONS ------------------------------
+impl ManualResetEvent {
+    /// Propagate sets of this event to `target`, and also resets if `propagate_reset` is `true`. Propagation runs on
+    /// the setter's thread, after the setter releases this event, until the returned guard drops.
+    ///
+    /// Fails with [`WaitObjectError::CycleDetected`] if `target` already propagates (directly or not) to this event.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use std::time::Duration;
+    /// use sync_wait_object::{EventGroup, ManualResetEvent, SignalWaitable, WaitObjectError};
+    ///
+    /// let mut child = ManualResetEvent::new();
+    /// let mut any_failed = ManualResetEvent::new();
+    ///
+    /// let _chain = child.chain_to(&any_failed, false).unwrap();
+    /// assert_eq!(any_failed.chain_to(&child, false).err(), Some(WaitObjectError::CycleDetected));
+    ///
+    /// child.set().unwrap();
+    /// assert!(any_failed.wait(Duration::ZERO).is_ok());
+    ///
+    /// // the target may be set together with its source
+    /// EventGroup::set_all(&[&mut child, &mut any_failed]).unwrap();
+    /// ```
+    pub fn chain_to(&self, target: &ManualResetEvent, propagate_reset: bool) -> Result<ChainGuard> {
+        let edge = (Arc::downgrade(&self.0.0), Arc::downgrade(&target.0.0));
+        {
+            let mut chains = CHAINS.lock()?;
+            if reaches(&chains, &edge.1, &edge.0) { return Err(WaitObjectError::CycleDetected) }
+            chains.push(edge.clone());
+        }
+        let link = Arc::new(Link { target: target.0.clone(), queued: AtomicU64::new(0), applied: Mutex::new(0) });
+        let subscription = self.0.on_change(move |v| {
+            if *v || propagate_reset {
+                // numbered under the source's lock, so the target ends in the latest state of the source
+                let seq = link.queued.fetch_add(1, Ordering::Relaxed) + 1;
+                DEFERRED.with(|d| d.borrow_mut().push_back((link.clone(), seq, *v)));
+            }
+        });
+        Ok(ChainGuard { _subscription: subscription, edge })
+    }
+}
+
+impl Drop for ChainGuard {
+    fn drop(&mut self) {
+        let mut chains = CHAINS.lock().unwrap_or_else(|e| e.into_inner());
+        if let Some(i) = chains.iter().position(|(s, t)| s.ptr_eq(&self.edge.0) && t.ptr_eq(&self.edge.1)) {
+            chains.swap_remove(i);
+        }
+    }
+}
+
+impl Drop for PropagatingGuard {
+    #[inline]
+    fn drop(&mut self) {
+        PROPAGATING.with(|p| p.set(false));
+    }
+}
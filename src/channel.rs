@@ -0,0 +1,108 @@
+use std::{ sync::mpsc::{ self, Receiver, SendError, Sender, TryRecvError }, time::Duration };
+use crate::{ Deadline, ManualResetEvent, WaitEvent, WaitObjectError, Result };
+
+// ------------------------------ DATA TYPES ----------------------------------
+/// Sending side of a channel from [`signaling_channel`], which signals the receiver's
+/// [`available`](SignalingReceiver::available) event on every send.
+pub struct SignalingSender<T> {
+    tx: Sender<T>,
+    pending: WaitEvent<usize>
+}
+
+/// Receiving side of an `std::sync::mpsc` channel with a "message available" event, so the channel can be waited for
+/// together with other events. Only messages sent through a [`SignalingSender`] are signaled.
+///
+/// # Examples
+///
+/// ```rust
+/// # use std::{thread, time::Duration};
+/// use sync_wait_object::{ManualResetEvent, signaling_channel};
+///
+/// let (tx, rx) = signaling_channel();
+/// let shutdown = ManualResetEvent::new();
+///
+/// thread::spawn(move || tx.send("job").unwrap());
+///
+/// let set = ManualResetEvent::wait_quorum(&[rx.available(), &shutdown], 1, Some(Duration::from_secs(1))).unwrap();
+/// assert_eq!(set, vec![0]);
+/// assert_eq!(rx.recv_timeout(Some(Duration::from_secs(1))), Ok("job"));
+/// ```
+pub struct SignalingReceiver<T> {
+    rx: Receiver<T>,
+    pending: WaitEvent<usize>,
+    available: ManualResetEvent
+}
+
+// ------------------------------ FUNCTIONS -------------------------------------
+/// Create an `std::sync::mpsc` channel whose receiver exposes a "message available" event.
+pub fn signaling_channel<T>() -> (SignalingSender<T>, SignalingReceiver<T>) {
+    let (tx, rx) = mpsc::channel();
+    let rx = SignalingReceiver::new(rx);
+    let tx = rx.wrap_sender(tx);
+    (tx, rx)
+}
+
+// ------------------------------ IMPLEMENTATIONS ------------------------------
+impl<T> SignalingSender<T> {
+    pub fn send(&self, value: T) -> std::result::Result<(), SendError<T>> {
+        // counted before sending, so the count never falls behind the messages in the channel
+        let _ = self.pending.modify(|n| *n += 1);
+        self.tx.send(value).inspect_err(|_| { let _ = self.pending.modify(|n| *n -= 1); })
+    }
+}
+
+impl<T> Clone for SignalingSender<T> {
+    fn clone(&self) -> Self {
+        Self { tx: self.tx.clone(), pending: self.pending.clone() }
+    }
+}
+
+impl<T> SignalingReceiver<T> {
+    /// Wrap an existing receiver. Use [`wrap_sender`](Self::wrap_sender) for its senders to signal.
+    pub fn new(rx: Receiver<T>) -> Self {
+        let pending = WaitEvent::new_init(0usize);
+        let available = pending.derive_signal(|n| *n > 0).expect("new event cannot be poisoned");
+        Self { rx, pending, available }
+    }
+
+    /// Wrap a sender of the same channel, so its sends signal this receiver.
+    pub fn wrap_sender(&self, tx: Sender<T>) -> SignalingSender<T> {
+        SignalingSender { tx, pending: self.pending.clone() }
+    }
+
+    /// Event which is set while signaled messages are pending.
+    #[inline]
+    pub fn available(&self) -> &ManualResetEvent { &self.available }
+
+    /// Receive a pending message, if any. A sent message may be signaled shortly before it can be received. Fails with
+    /// [`WaitObjectError::Disconnected`] once all senders are dropped and the channel is empty.
+    pub fn try_recv(&self) -> Result<Option<T>> {
+        match self.rx.try_recv() {
+            Ok(v) => {
+                self.pending.modify(|n| *n = n.saturating_sub(1))?;
+                Ok(Some(v))
+            },
+            Err(TryRecvError::Empty) => Ok(None),
+            Err(TryRecvError::Disconnected) => Err(WaitObjectError::Disconnected)
+        }
+    }
+
+    /// Receive a message, waiting at most `timeout` in total for one to be signaled and received.
+    pub fn recv_timeout(&self, timeout: Option<Duration>) -> Result<T> {
+        let deadline = Deadline::from_timeout(timeout);
+        drop(self.pending.wait(timeout, |n| *n > 0)?);
+        match deadline.remaining() {
+            Some(t) => self.rx.recv_timeout(t).map_err(|e| match e {
+                mpsc::RecvTimeoutError::Timeout => WaitObjectError::Timeout,
+                mpsc::RecvTimeoutError::Disconnected => WaitObjectError::Disconnected
+            }),
+            None => self.rx.recv().map_err(|_| WaitObjectError::Disconnected)
+        }.and_then(|v| {
+            self.pending.modify(|n| *n = n.saturating_sub(1))?;
+            Ok(v)
+        })
+    }
+
+    #[inline]
+    pub fn into_inner(self) -> Receiver<T> { self.rx }
+}
@@ -0,0 +1,65 @@
+use std::time::Duration;
+use crate::{ WaitEvent, Result };
+
+// ------------------------------ DATA TYPES ----------------------------------
+/// Multi-consumer event where each published item is claimed by exactly one waiting consumer, while the others keep
+/// waiting. Checking and taking the item happen under the same lock, so no two consumers can claim the same item.
+///
+/// # Examples
+///
+/// ```rust
+/// # use std::{thread, time::Duration};
+/// use sync_wait_object::ClaimEvent;
+///
+/// let work = ClaimEvent::new();
+/// let consumers: Vec<_> = (0..3).map(|_| {
+///     let work = work.clone();
+///     thread::spawn(move || work.claim(Some(Duration::from_millis(200))).ok())
+/// }).collect();
+///
+/// let mut producer = work.clone();
+/// producer.publish("job").unwrap();
+///
+/// let claimed: Vec<_> = consumers.into_iter().filter_map(|c| c.join().unwrap()).collect();
+/// assert_eq!(claimed, vec!["job"]);
+/// ```
+pub struct ClaimEvent<T>(WaitEvent<Option<T>>);
+
+// ------------------------------ IMPLEMENTATIONS ------------------------------
+impl<T> ClaimEvent<T> {
+    #[inline]
+    pub fn new() -> Self { Self(WaitEvent::new_init(None)) }
+
+    /// Publish `item` for one consumer to claim. Returns the previously published item if no consumer claimed it yet.
+    pub fn publish(&mut self, item: T) -> Result<Option<T>> {
+        self.0.modify(|slot| slot.replace(item))
+    }
+
+    /// Wait until an item is published, and claim it.
+    ///
+    /// # Arguments
+    ///
+    /// * `timeout` - Maximum wait time
+    pub fn claim(&self, timeout: Option<Duration>) -> Result<T> {
+        self.0.wait_then(timeout, Option::is_some, |slot| slot.take().unwrap())
+    }
+
+    /// Claim the published item, if any, without waiting.
+    pub fn try_claim(&self) -> Result<Option<T>> {
+        self.0.modify(Option::take)
+    }
+
+    /// Whether an item is published and not claimed yet.
+    #[inline]
+    pub fn is_pending(&self) -> Result<bool> { self.0.value().map(|slot| slot.is_some()) }
+}
+
+impl<T> Clone for ClaimEvent<T> {
+    #[inline]
+    fn clone(&self) -> Self { Self(self.0.clone()) }
+}
+
+impl<T> Default for ClaimEvent<T> {
+    #[inline]
+    fn default() -> Self { Self::new() }
+}
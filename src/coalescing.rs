@@ -0,0 +1,66 @@
+use std::{ sync::Arc, time::Duration };
+use crate::{ WaitEvent, Result };
+
+// ------------------------------ DATA TYPES ----------------------------------
+/// Multi-producer event which merges submitted items with a fold closure until a consumer drains the aggregate
+/// ("dirty set" pattern).
+///
+/// # Examples
+///
+/// ```rust
+/// # use std::time::Duration;
+/// use sync_wait_object::CoalescingEvent;
+///
+/// let repaint = CoalescingEvent::new(|a: (u32, u32), b: (u32, u32)| (a.0.min(b.0), a.1.max(b.1)));
+/// let mut producer = repaint.clone();
+///
+/// producer.submit((10, 20)).unwrap();
+/// producer.submit((5, 12)).unwrap();
+///
+/// assert_eq!(repaint.drain(Some(Duration::from_secs(1))).unwrap(), (5, 20));
+/// assert_eq!(repaint.try_drain().unwrap(), None);
+/// ```
+pub struct CoalescingEvent<T> {
+    inner: WaitEvent<Option<T>>,
+    fold: Arc<dyn Fn(T, T) -> T + Send + Sync>
+}
+
+// ------------------------------ IMPLEMENTATIONS ------------------------------
+impl<T> CoalescingEvent<T> {
+    /// Create an empty event. `fold` merges the pending aggregate (first argument) with a newly submitted item.
+    pub fn new(fold: impl Fn(T, T) -> T + Send + Sync + 'static) -> Self {
+        Self { inner: WaitEvent::new_init(None), fold: Arc::new(fold) }
+    }
+
+    /// Merge `item` into the pending aggregate and wake the consumers.
+    pub fn submit(&mut self, item: T) -> Result<()> {
+        let fold = &self.fold;
+        self.inner.modify(|pending| {
+            let next = match pending.take() {
+                Some(acc) => fold(acc, item),
+                None => item
+            };
+            *pending = Some(next);
+        })
+    }
+
+    /// Wait until there is a pending aggregate, then take it out.
+    ///
+    /// # Arguments
+    ///
+    /// * `timeout` - Maximum wait time
+    pub fn drain(&self, timeout: Option<Duration>) -> Result<T> {
+        self.inner.wait_for_update(timeout.or_else(|| self.inner.unset_timeout()), Option::is_some).map(|mut pending| pending.take().unwrap())
+    }
+
+    /// Take the pending aggregate out, if any, without waiting.
+    pub fn try_drain(&self) -> Result<Option<T>> {
+        self.inner.lock_for_update().map(|mut pending| pending.take())
+    }
+}
+
+impl<T> Clone for CoalescingEvent<T> {
+    fn clone(&self) -> Self {
+        Self { inner: self.inner.clone(), fold: self.fold.clone() }
+    }
+}
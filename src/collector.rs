@@ -0,0 +1,86 @@
+use std::time::Duration;
+use crate::{ WaitEvent, Result };
+
+// ------------------------------ DATA TYPES ----------------------------------
+/// Scatter-gather helper: `expected` workers each [`submit`](ResultCollector::submit) one `Result<T, E>`, and the
+/// coordinator waits for all of them (or for the first one).
+///
+/// The first submitted error wins and ends [`wait_all`](ResultCollector::wait_all) early, without waiting for the
+/// remaining workers.
+///
+/// # Examples
+///
+/// ```rust
+/// # use std::{thread, time::Duration};
+/// use sync_wait_object::ResultCollector;
+///
+/// let collector = ResultCollector::<u32, String>::new(3);
+///
+/// for i in 0..3 {
+///     let mut worker = collector.clone();
+///     thread::spawn(move || worker.submit(Ok(i * 10)).unwrap());
+/// }
+///
+/// let mut values = collector.wait_all(Some(Duration::from_secs(1))).unwrap().unwrap();
+/// values.sort();
+/// assert_eq!(values, vec![0, 10, 20]);
+/// ```
+pub struct ResultCollector<T, E>(WaitEvent<Collected<T, E>>);
+
+struct Collected<T, E> {
+    expected: usize,
+    submitted: usize,
+    values: Vec<T>,
+    error: Option<E>
+}
+
+// ------------------------------ IMPLEMENTATIONS ------------------------------
+impl<T, E> ResultCollector<T, E> {
+    /// Create a collector which expects `expected` submissions.
+    pub fn new(expected: usize) -> Self {
+        Self(WaitEvent::new_init(Collected { expected, submitted: 0, values: Vec::with_capacity(expected), error: None }))
+    }
+
+    /// Submit a worker result. Only the first error is kept; later errors are dropped.
+    pub fn submit(&mut self, result: std::result::Result<T, E>) -> Result<()> {
+        self.0.modify(|state| {
+            state.submitted += 1;
+            match result {
+                Ok(v) => state.values.push(v),
+                Err(e) => if state.error.is_none() { state.error = Some(e) }
+            }
+        })
+    }
+
+    /// Wait until all expected results are submitted, or an error is submitted. Collected values are taken out of
+    /// the collector, in submission order.
+    ///
+    /// # Arguments
+    ///
+    /// * `timeout` - Maximum wait time
+    pub fn wait_all(&self, timeout: Option<Duration>) -> Result<std::result::Result<Vec<T>, E>> {
+        self.0.wait_then(timeout, |s| s.error.is_some() || s.submitted >= s.expected, |state| match state.error.take() {
+            Some(e) => Err(e),
+            None => Ok(std::mem::take(&mut state.values))
+        })
+    }
+
+    /// Wait until any value or error is available, and take it out of the collector. An error takes priority over
+    /// values.
+    ///
+    /// # Arguments
+    ///
+    /// * `timeout` - Maximum wait time
+    pub fn wait_first(&self, timeout: Option<Duration>) -> Result<std::result::Result<T, E>> {
+        self.0.wait_then(timeout, |s| s.error.is_some() || !s.values.is_empty(), |state| match state.error.take() {
+            Some(e) => Err(e),
+            None => Ok(state.values.remove(0))
+        })
+    }
+}
+
+impl<T, E> Clone for ResultCollector<T, E> {
+    fn clone(&self) -> Self {
+        Self(self.0.clone())
+    }
+}
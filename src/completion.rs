@@ -0,0 +1,122 @@
+use std::time::Duration;
+use crate::{ ManualResetEvent, WaitEvent, Result, SignalWaitable };
+
+type Outcome<T, E> = std::result::Result<T, E>;
+
+// ------------------------------ DATA TYPES ----------------------------------
+/// One-shot outcome of an operation: completed once with either a value or an error, which every waiter then observes.
+/// The [`done`](Completion::done) event is set on completion, to compose with other events. Clones share the same
+/// state.
+///
+/// # Examples
+///
+/// ```rust
+/// # use std::{thread, time::Duration};
+/// use sync_wait_object::Completion;
+///
+/// let upload = Completion::<usize, String>::new();
+/// let mut worker = upload.clone();
+/// thread::spawn(move || worker.complete(Ok(1024)).unwrap());
+///
+/// assert_eq!(upload.wait(Some(Duration::from_secs(1))).unwrap(), Ok(1024));
+/// assert!(upload.is_complete().unwrap());
+/// ```
+pub struct Completion<T, E> {
+    outcome: WaitEvent<Option<Outcome<T, E>>>,
+    done: ManualResetEvent
+}
+
+// ------------------------------ IMPLEMENTATIONS ------------------------------
+impl<T, E> Completion<T, E> {
+    pub fn new() -> Self {
+        Self { outcome: WaitEvent::new_init(None), done: ManualResetEvent::new() }
+    }
+
+    /// Complete with `outcome`, waking all waiters.
+    ///
+    /// # Panics
+    ///
+    /// Panics if already completed. Use [`try_complete`](Self::try_complete) when several parties race to complete.
+    ///
+    /// ```rust,should_panic
+    /// use sync_wait_object::Completion;
+    ///
+    /// let mut job = Completion::<u8, ()>::new();
+    /// job.complete(Ok(1)).unwrap();
+    /// job.complete(Ok(2)).unwrap();
+    /// ```
+    pub fn complete(&mut self, outcome: Outcome<T, E>) -> Result<()> {
+        match self.try_complete(outcome)? {
+            Ok(()) => Ok(()),
+            Err(_) => panic!("Completion is already completed")
+        }
+    }
+
+    /// Complete with `outcome` if not completed yet, waking all waiters. Otherwise, the outcome is returned back.
+    pub fn try_complete(&mut self, outcome: Outcome<T, E>) -> Result<std::result::Result<(), Outcome<T, E>>> {
+        let r = self.outcome.modify(|slot| match slot {
+            Some(_) => Err(outcome),
+            None => { *slot = Some(outcome); Ok(()) }
+        })?;
+        if r.is_ok() { self.done.set()? }
+        Ok(r)
+    }
+
+    #[inline]
+    pub fn is_complete(&self) -> Result<bool> { self.outcome.value().map(|slot| slot.is_some()) }
+
+    /// Event set once completed.
+    #[inline]
+    pub fn done(&self) -> ManualResetEvent { self.done.clone() }
+
+    /// Wait until completed, and run `f` on the outcome.
+    ///
+    /// # Arguments
+    ///
+    /// * `timeout` - Maximum wait time
+    /// * `f` - Function reading the outcome
+    pub fn wait_with<R>(&self, timeout: Option<Duration>, f: impl FnOnce(&Outcome<T, E>) -> R) -> Result<R> {
+        self.outcome.wait_map(timeout, Option::is_some, |slot| f(slot.as_ref().unwrap()))
+    }
+}
+
+impl<T: Clone, E: Clone> Completion<T, E> {
+    /// Copy of the outcome, if completed.
+    #[inline]
+    pub fn get(&self) -> Result<Option<Outcome<T, E>>> { self.outcome.peek() }
+
+    /// Wait until completed, and return a copy of the outcome.
+    ///
+    /// # Arguments
+    ///
+    /// * `timeout` - Maximum wait time
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use std::time::Duration;
+    /// use sync_wait_object::{Completion, WaitObjectError};
+    ///
+    /// let job = Completion::<(), &str>::new();
+    /// assert!(matches!(job.wait(Some(Duration::from_millis(10))), Err(WaitObjectError::Timeout)));
+    ///
+    /// job.clone().complete(Err("cancelled")).unwrap();
+    /// assert_eq!(job.wait(None).unwrap(), Err("cancelled"));
+    /// assert_eq!(job.clone().try_complete(Ok(())).unwrap(), Err(Ok(())));
+    /// ```
+    #[inline]
+    pub fn wait(&self, timeout: Option<Duration>) -> Result<Outcome<T, E>> {
+        self.wait_with(timeout, Outcome::clone)
+    }
+}
+
+impl<T, E> Clone for Completion<T, E> {
+    fn clone(&self) -> Self {
+        Self { outcome: self.outcome.clone(), done: self.done.clone() }
+    }
+}
+
+impl<T, E> Default for Completion<T, E> {
+    #[inline]
+    fn default() -> Self { Self::new() }
+}
@@ -0,0 +1,149 @@
+use std::{ cell::Cell, time::{ Duration, Instant } };
+use crate::{ WaitObjectError, Result };
+
+// ------------------------------ DATA TYPES ----------------------------------
+/// Overall time budget shared by a sequence of waits.
+///
+/// A deadline can be passed to each wait with [`timeout`](Self::timeout), or installed for the current thread with
+/// [`scope`](Self::scope), in which case every wait inside the scope is bounded by it without any change to the call.
+/// Either way, once the budget is exhausted, waits fail with [`WaitObjectError::Timeout`] right away.
+///
+/// # Examples
+///
+/// ```rust
+/// # use std::time::Duration;
+/// use sync_wait_object::{Deadline, WaitEvent, WaitObjectError};
+///
+/// let connected = WaitEvent::new_init(true);
+/// let handshake = WaitEvent::new_init(false);
+/// let deadline = Deadline::after(Duration::from_millis(20));
+///
+/// let r = deadline.scope(|| {
+///     connected.wait(None, |v| *v)?;
+///     handshake.wait(None, |v| *v)?;
+///     Ok(())
+/// });
+/// assert_eq!(r, Err(WaitObjectError::Timeout));
+/// assert!(deadline.is_expired());
+/// assert_eq!(deadline.timeout(), Err(WaitObjectError::Timeout));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Deadline(Option<Instant>);
+
+thread_local! {
+    static SCOPED: Cell<Deadline> = const { Cell::new(Deadline::NEVER) };
+}
+
+/// Restores the previous thread deadline when a scope ends, even by panic.
+struct ScopeGuard(Deadline);
+
+// ------------------------------ IMPLEMENTATIONS ------------------------------
+impl Deadline {
+    /// Deadline which never expires.
+    pub const NEVER: Self = Self(None);
+
+    /// Deadline expiring after `budget` from now. A budget too large to be represented as an instant (such as
+    /// `Duration::MAX`) never expires.
+    #[inline]
+    pub fn after(budget: Duration) -> Self {
+        Self(Instant::now().checked_add(budget))
+    }
+
+    /// Deadline of a wait `timeout`, with the semantics shared by every backend (portable and native):
+    ///
+    /// * `None` and `Some(Duration::MAX)` (or any duration too large to be represented as an instant) never expire;
+    /// * `Some(Duration::ZERO)` expires right away, so the wait only tries once.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use std::time::Duration;
+    /// use sync_wait_object::Deadline;
+    ///
+    /// assert_eq!(Deadline::from_timeout(None), Deadline::NEVER);
+    /// assert_eq!(Deadline::from_timeout(Some(Duration::MAX)), Deadline::NEVER);
+    /// assert!(Deadline::from_timeout(Some(Duration::ZERO)).is_expired());
+    /// ```
+    #[inline]
+    pub fn from_timeout(timeout: Option<Duration>) -> Self {
+        timeout.map_or(Self::NEVER, Self::after)
+    }
+
+    #[inline]
+    pub fn at(instant: Instant) -> Self { Self(Some(instant)) }
+
+    #[inline]
+    pub fn instant(&self) -> Option<Instant> { self.0 }
+
+    /// Remaining budget, or `None` if unbounded.
+    #[inline]
+    pub fn remaining(&self) -> Option<Duration> {
+        self.0.map(|d| d.saturating_duration_since(Instant::now()))
+    }
+
+    #[inline]
+    pub fn is_expired(&self) -> bool {
+        self.0.is_some_and(|d| Instant::now() >= d)
+    }
+
+    /// Timeout to give to the next wait, or [`WaitObjectError::Timeout`] if the budget is exhausted.
+    pub fn timeout(&self) -> Result<Option<Duration>> {
+        if self.is_expired() { Err(WaitObjectError::Timeout) } else { Ok(self.remaining()) }
+    }
+
+    /// Earlier of the two deadlines.
+    pub fn min(self, other: Self) -> Self {
+        match (self.0, other.0) {
+            (Some(a), Some(b)) => Self(Some(a.min(b))),
+            (a, b) => Self(a.or(b))
+        }
+    }
+
+    /// Deadline in effect on the current thread, [`NEVER`](Self::NEVER) outside of any scope.
+    #[inline]
+    pub fn current() -> Self {
+        SCOPED.with(Cell::get)
+    }
+
+    /// Run `f` with this deadline bounding all waits on the current thread. Nested scopes can only shorten the
+    /// deadline in effect.
+    pub fn scope<R>(self, f: impl FnOnce() -> R) -> R {
+        let _guard = ScopeGuard(SCOPED.with(|s| s.replace(self.min(s.get()))));
+        f()
+    }
+
+    /// Remaining budget in whole milliseconds for Win32 waits: `INFINITE` if unbounded, rounded up so a sub-millisecond
+    /// remainder does not turn into a single try, and capped below `INFINITE` for very long finite budgets (the caller
+    /// waits again until the deadline expires).
+    #[cfg(windows)]
+    pub(crate) fn win32_millis(&self) -> u32 {
+        const INFINITE: u32 = u32::MAX;
+        match self.remaining() {
+            None => INFINITE,
+            Some(r) => {
+                let millis = r.as_nanos().div_ceil(1_000_000);
+                u32::try_from(millis).unwrap_or(INFINITE - 1).min(INFINITE - 1)
+            }
+        }
+    }
+
+    /// Bound `timeout` by the deadline of the current thread.
+    pub(crate) fn limit(timeout: Option<Duration>) -> Result<Option<Duration>> {
+        let remaining = Self::current().timeout()?;
+        Ok(match (timeout, remaining) {
+            (Some(t), Some(r)) => Some(t.min(r)),
+            (t, r) => t.or(r)
+        })
+    }
+}
+
+impl Default for Deadline {
+    #[inline]
+    fn default() -> Self { Self::NEVER }
+}
+
+impl Drop for ScopeGuard {
+    fn drop(&mut self) {
+        SCOPED.with(|s| s.set(self.0));
+    }
+}
@@ -0,0 +1,74 @@
+//! Detection of abandoned waiters. Only available with the `debug` feature.
+//!
+//! When a handle of an event drops and the remaining handles are no more than the threads blocked on the event, which
+//! no set has woken up yet, the waiters are likely abandoned: nobody else is left to set the event, and they would
+//! hang forever. Setters blocked by a [`hold`](crate::WaitEvent::wait_and_hold) or a
+//! [`freeze`](crate::WaitEvent::freeze) are not counted. This is reported
+//! on stderr, or by panicking (see [`set_abandon_action`]), with the event name (see
+//! [`WaitEventBuilder::name`](crate::WaitEventBuilder::name)) and the waiter count.
+//!
+//! Since several waiters may share one handle, this is a heuristic which can report a false positive.
+//!
+//! ```rust,should_panic
+//! # use std::{thread, time::Duration};
+//! use sync_wait_object::{WaitEvent, debug::{self, AbandonAction}};
+//!
+//! debug::set_abandon_action(AbandonAction::Panic);
+//!
+//! let ev = WaitEvent::builder().name("config-loaded").build(false);
+//! let waiter = ev.clone();
+//! thread::spawn(move || drop(waiter.wait(None, |v| *v)));
+//! while !ev.has_waiters() { thread::sleep(Duration::from_millis(1)) }
+//!
+//! drop(ev);   // panics: 1 waiter abandoned on event "config-loaded"
+//! ```
+#![cfg_attr(feature = "testing", doc = r#"
+A waiter which was set, but has not returned yet, is not abandoned:
+
+```rust
+# use std::{sync::Arc, thread, time::Duration};
+use sync_wait_object::{WaitEvent, debug::{self, AbandonAction}, testing::{self, SchedulePoint}};
+
+debug::set_abandon_action(AbandonAction::Panic);
+// hold woken waiters long enough for the setter to drop its handle first
+testing::set_scheduler(Some(Arc::new(|point, _| if point == SchedulePoint::Wake { thread::sleep(Duration::from_millis(50)) })));
+
+let ev = WaitEvent::new_init(false);
+let waiter = ev.clone();
+let worker = thread::spawn(move || drop(waiter.wait(None, |v| *v).unwrap()));
+while !ev.has_waiters() { thread::yield_now() }
+
+let mut setter = ev;
+setter.set_state(true).unwrap();
+drop(setter);
+worker.join().unwrap();
+```
+"#)]
+
+use std::sync::atomic::{ AtomicU8, Ordering };
+
+/// Reaction to abandoned waiters.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum AbandonAction {
+    /// Print a message on stderr
+    #[default]
+    Log,
+
+    /// Panic in the thread dropping the handle
+    Panic
+}
+
+static ACTION: AtomicU8 = AtomicU8::new(AbandonAction::Log as u8);
+
+/// Select how abandoned waiters are reported, process-wide.
+pub fn set_abandon_action(action: AbandonAction) {
+    ACTION.store(action as u8, Ordering::SeqCst);
+}
+
+pub(crate) fn report_abandoned(name: Option<&str>, waiters: usize) {
+    let message = format!("{waiters} waiter(s) abandoned on event {}", name.map_or("<unnamed>".to_string(), |n| format!("\"{n}\"")));
+    if ACTION.load(Ordering::SeqCst) == AbandonAction::Panic as u8 {
+        panic!("{message}");
+    }
+    eprintln!("{message}");
+}
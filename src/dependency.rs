@@ -0,0 +1,125 @@
+use std::{ collections::{ HashMap, HashSet }, hash::Hash, sync::{ Arc, Mutex }, thread, time::Duration };
+use crate::{ CancellationToken, Deadline, ManualResetEvent, SignalWaitable, WaitPolicy, WaitObjectError, Result, ANY_POLL_INTERVAL };
+
+// ------------------------------ DATA TYPES ----------------------------------
+/// Graph of events with dependencies: waiting for a node waits until the node and all its transitive dependencies are
+/// set. Dependencies may be registered after their dependents, and cycles are rejected at registration.
+///
+/// # Examples
+///
+/// ```rust
+/// # use std::time::Duration;
+/// use sync_wait_object::{DependencyGraph, ManualResetEvent, SignalWaitable, WaitObjectError};
+///
+/// let graph = DependencyGraph::new();
+/// let (mut fetch, mut compile, mut link) = (ManualResetEvent::new(), ManualResetEvent::new(), ManualResetEvent::new());
+/// graph.register("fetch", fetch.clone(), []).unwrap();
+/// graph.register("compile", compile.clone(), ["fetch"]).unwrap();
+/// graph.register("link", link.clone(), ["compile"]).unwrap();
+/// assert_eq!(graph.register("fetch", fetch.clone(), ["link"]), Err(WaitObjectError::CycleDetected));
+///
+/// link.set().unwrap();
+/// compile.set().unwrap();
+/// assert!(graph.wait_for(&"link", Some(Duration::from_millis(10))).is_err());
+///
+/// fetch.set().unwrap();
+/// assert!(graph.wait_for(&"link", Some(Duration::from_millis(10))).is_ok());
+/// ```
+pub struct DependencyGraph<K>(Arc<Mutex<HashMap<K, Node<K>>>>);
+
+struct Node<K> {
+    event: ManualResetEvent,
+    deps: Vec<K>
+}
+
+// ------------------------------ IMPLEMENTATIONS ------------------------------
+impl<K: Eq + Hash + Clone> DependencyGraph<K> {
+    #[inline]
+    pub fn new() -> Self { Self(Arc::new(Mutex::new(HashMap::new()))) }
+
+    /// Register (or replace) node `key` signaled by `event`, which depends on `deps`. Fails with
+    /// [`WaitObjectError::CycleDetected`] if a dependency (transitively) depends on `key`.
+    pub fn register(&self, key: K, event: ManualResetEvent, deps: impl IntoIterator<Item = K>) -> Result<()> {
+        let deps: Vec<K> = deps.into_iter().collect();
+        let mut nodes = self.0.lock()?;
+        let mut visited = HashSet::new();
+        let mut stack: Vec<&K> = deps.iter().collect();
+        while let Some(k) = stack.pop() {
+            if *k == key { return Err(WaitObjectError::CycleDetected) }
+            if visited.insert(k) {
+                stack.extend(nodes.get(k).into_iter().flat_map(|n| n.deps.iter()));
+            }
+        }
+        nodes.insert(key, Node { event, deps });
+        Ok(())
+    }
+
+    /// Wait until node `key` and all its transitive dependencies are registered and set, or timed-out from
+    /// `timeout` (`None` for the default timeout of the event of `key`, see [`WaitPolicy`]).
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use std::time::Duration;
+    /// use sync_wait_object::{Deadline, DependencyGraph, ManualResetEvent, WaitObjectError};
+    ///
+    /// let graph = DependencyGraph::new();
+    /// graph.register("link", ManualResetEvent::new(), ["compile"]).unwrap();
+    ///
+    /// let r = Deadline::after(Duration::from_millis(10)).scope(|| graph.wait_for(&"link", None));
+    /// assert_eq!(r, Err(WaitObjectError::Timeout));
+    /// ```
+    pub fn wait_for(&self, key: &K, timeout: Option<Duration>) -> Result<()> {
+        let timeout = match timeout {
+            Some(t) => Some(t),
+            None => {
+                let event_timeout = self.0.lock()?.get(key).and_then(|n| n.event.0.unset_timeout());
+                event_timeout.or_else(|| WaitPolicy::current().unset_timeout(None))
+            }
+        };
+        let deadline = Deadline::from_timeout(Deadline::limit(timeout)?);
+        loop {
+            let event = match self.first_unset(key)? {
+                None => return Ok(()),
+                Some(_) if deadline.is_expired() => return Err(WaitObjectError::Timeout),
+                Some(_) if CancellationToken::scope_cancelled() => return Err(WaitObjectError::Cancelled),
+                Some(event) => event
+            };
+            let slice = deadline.remaining().map_or(ANY_POLL_INTERVAL, |r| r.min(ANY_POLL_INTERVAL));
+            match event {
+                Some(event) => match event.wait(slice) {
+                    Ok(()) | Err(WaitObjectError::Timeout) => (),
+                    Err(e) => return Err(e)
+                },
+                // not registered yet
+                None => thread::sleep(slice)
+            }
+        }
+    }
+
+    /// First node of the closure of `key` which is not set: `Some(Some(event))`, or `Some(None)` if a node is not
+    /// registered.
+    fn first_unset(&self, key: &K) -> Result<Option<Option<ManualResetEvent>>> {
+        let nodes = self.0.lock()?;
+        let mut visited = HashSet::new();
+        let mut stack = vec![key];
+        while let Some(k) = stack.pop() {
+            if !visited.insert(k) { continue }
+            let Some(node) = nodes.get(k) else { return Ok(Some(None)) };
+            if !node.event.is_set()? { return Ok(Some(Some(node.event.clone()))) }
+            stack.extend(node.deps.iter());
+        }
+        Ok(None)
+    }
+}
+
+impl<K: Eq + Hash + Clone> Default for DependencyGraph<K> {
+    #[inline]
+    fn default() -> Self { Self::new() }
+}
+
+impl<K> Clone for DependencyGraph<K> {
+    fn clone(&self) -> Self {
+        Self(self.0.clone())
+    }
+}
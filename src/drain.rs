@@ -0,0 +1,88 @@
+use std::time::Duration;
+use crate::{ WaitEvent, Result };
+
+// ------------------------------ DATA TYPES ----------------------------------
+/// Gate for the quiesce/drain pattern: workers hold [`ActivityGuard`]s while active, and a controller closes the gate
+/// and waits until all outstanding guards drop, e.g. before reconfiguration.
+///
+/// # Examples
+///
+/// ```rust
+/// # use std::{thread, time::Duration};
+/// use sync_wait_object::DrainGate;
+///
+/// let gate = DrainGate::new();
+/// let activity = gate.enter(None).unwrap();
+///
+/// thread::spawn(move || {
+///     thread::sleep(Duration::from_millis(20));
+///     drop(activity);
+/// });
+///
+/// gate.close_and_wait(Some(Duration::from_secs(1))).unwrap();
+/// assert!(gate.try_enter().unwrap().is_none());
+///
+/// gate.reopen().unwrap();
+/// assert!(gate.try_enter().unwrap().is_some());
+/// ```
+#[derive(Clone)]
+pub struct DrainGate(WaitEvent<GateState>);
+
+/// Activity registered in a [`DrainGate`], released when dropped.
+#[must_use = "the activity ends when the guard drops"]
+pub struct ActivityGuard(WaitEvent<GateState>);
+
+struct GateState {
+    open: bool,
+    active: usize
+}
+
+// ------------------------------ IMPLEMENTATIONS ------------------------------
+impl DrainGate {
+    #[inline]
+    pub fn new() -> Self {
+        Self(WaitEvent::new_init(GateState { open: true, active: 0 }))
+    }
+
+    /// Number of outstanding activity guards.
+    pub fn active(&self) -> Result<usize> {
+        self.0.value().map(|s| s.active)
+    }
+
+    /// Wait until the gate is open, or timed-out from `timeout`, then register an activity.
+    pub fn enter(&self, timeout: Option<Duration>) -> Result<ActivityGuard> {
+        self.0.wait_then(timeout, |s| s.open, |s| s.active += 1)?;
+        Ok(ActivityGuard(self.0.clone()))
+    }
+
+    /// Register an activity if the gate is open.
+    pub fn try_enter(&self) -> Result<Option<ActivityGuard>> {
+        let entered = self.0.modify(|s| {
+            if s.open { s.active += 1 }
+            s.open
+        })?;
+        Ok(entered.then(|| ActivityGuard(self.0.clone())))
+    }
+
+    /// Close the gate, then wait until all outstanding activities end, or timed-out from `timeout`. The gate stays
+    /// closed until [`reopen`](Self::reopen), even on timeout.
+    pub fn close_and_wait(&self, timeout: Option<Duration>) -> Result<()> {
+        self.0.modify(|s| s.open = false)?;
+        self.0.wait(timeout, |s| s.active == 0).map(|_| ())
+    }
+
+    /// Open the gate, and wake the workers waiting to enter.
+    pub fn reopen(&self) -> Result<()> {
+        self.0.modify(|s| s.open = true)
+    }
+}
+
+impl Default for DrainGate {
+    #[inline] fn default() -> Self { Self::new() }
+}
+
+impl Drop for ActivityGuard {
+    fn drop(&mut self) {
+        let _ = self.0.modify(|s| s.active -= 1);
+    }
+}
@@ -0,0 +1,114 @@
+use std::time::Duration;
+use crate::{ CancellationToken, Deadline, ManualResetEvent, WaitEvent, Result, WaitObjectError };
+
+// ------------------------------ DATA TYPES ----------------------------------
+/// Manual-reset event which carries an error of type `E` while set. Paired with a [`ManualResetEvent`] for success,
+/// [`wait_result`] waits for either outcome. Clones share the same state.
+///
+/// # Examples
+///
+/// ```rust
+/// # use std::time::Duration;
+/// use sync_wait_object::ErrorEvent;
+///
+/// let failed = ErrorEvent::new();
+/// let mut reporter = failed.clone();
+///
+/// reporter.set("disk full").unwrap();
+/// assert_eq!(failed.wait(Some(Duration::from_secs(1))).unwrap(), "disk full");
+/// ```
+pub struct ErrorEvent<E>(WaitEvent<Option<E>>);
+
+// ------------------------------ FUNCTIONS -------------------------------------
+/// Wait until either `ok_event` or `err_event` is set, or timed-out from `timeout`. Returns `Ok(())` for success, or a
+/// copy of the error. The error wins when both are set.
+///
+/// Like [`WaitEvent::wait_any_of`], the two events are polled in turn with a short interval
+/// ([`ANY_POLL_INTERVAL`](crate::ANY_POLL_INTERVAL)).
+///
+/// # Arguments
+///
+/// * `ok_event` - Event set on success
+/// * `err_event` - Event set with the error on failure
+/// * `timeout` - Maximum wait time, `None` for the events' default timeout (see [`WaitPolicy`](crate::WaitPolicy))
+///
+/// # Examples
+///
+/// ```rust
+/// # use std::{thread, time::Duration};
+/// use sync_wait_object::{ErrorEvent, ManualResetEvent, SignalWaitable, wait_result};
+///
+/// let (done, failed) = (ManualResetEvent::new(), ErrorEvent::<String>::new());
+/// let (mut job_done, mut job_failed) = (done.clone(), failed.clone());
+///
+/// thread::spawn(move || match "42".parse::<u32>() {
+///     Ok(_) => job_done.set().unwrap(),
+///     Err(e) => job_failed.set(e.to_string()).unwrap()
+/// });
+///
+/// assert_eq!(wait_result(&done, &failed, Some(Duration::from_secs(1))).unwrap(), Ok(()));
+/// ```
+pub fn wait_result<E: Clone>(ok_event: &ManualResetEvent, err_event: &ErrorEvent<E>, timeout: Option<Duration>) -> Result<std::result::Result<(), E>> {
+    let timeout = timeout.or_else(|| ok_event.0.unset_timeout().into_iter().chain(err_event.0.unset_timeout()).min());
+    let deadline = Deadline::from_timeout(Deadline::limit(timeout)?);
+    let mut turn = 0;
+    loop {
+        if let Some(e) = &*err_event.0.lock_state()? { return Ok(Err(e.clone())) }
+        if *ok_event.0.lock_state()? { return Ok(Ok(())) }
+        if deadline.is_expired() { return Err(WaitObjectError::Timeout) }
+        if CancellationToken::scope_cancelled() { return Err(WaitObjectError::Cancelled) }
+
+        if turn % 2 == 0 { ok_event.0.park_slice()? } else { err_event.0.park_slice()? }
+        turn += 1;
+    }
+}
+
+// ------------------------------ IMPLEMENTATIONS ------------------------------
+impl<E> ErrorEvent<E> {
+    #[inline]
+    pub fn new() -> Self { Self(WaitEvent::new_init(None)) }
+
+    /// Set the event with `error`, replacing any previous error, and wake the waiters.
+    #[inline]
+    pub fn set(&mut self, error: E) -> Result<()> {
+        self.0.set_state(Some(error))
+    }
+
+    /// Clear the error.
+    #[inline]
+    pub fn reset(&mut self) -> Result<()> {
+        self.0.set_state(None)
+    }
+
+    #[inline]
+    pub fn is_set(&self) -> Result<bool> {
+        self.0.value().map(|e| e.is_some())
+    }
+}
+
+impl<E: Clone> ErrorEvent<E> {
+    /// Copy of the error, if set.
+    #[inline]
+    pub fn error(&self) -> Result<Option<E>> {
+        self.0.peek()
+    }
+
+    /// Wait until the event is set, or timed-out from `timeout`, and return a copy of the error.
+    ///
+    /// # Arguments
+    ///
+    /// * `timeout` - Maximum wait time
+    pub fn wait(&self, timeout: Option<Duration>) -> Result<E> {
+        self.0.wait_map(timeout, Option::is_some, |e| e.clone().expect("checked to be Some"))
+    }
+}
+
+impl<E> Clone for ErrorEvent<E> {
+    fn clone(&self) -> Self {
+        Self(self.0.clone())
+    }
+}
+
+impl<E> Default for ErrorEvent<E> {
+    #[inline] fn default() -> Self { Self::new() }
+}
@@ -0,0 +1,129 @@
+use std::time::Duration;
+use crate::{ WaitEvent, Result, SignalWaitable };
+
+// ------------------------------ DATA TYPES ----------------------------------
+/// Latching behavior of an [`Event`], selected at construction.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum EventMode {
+    /// Stay set until explicitly reset, like [`ManualResetEvent`](crate::ManualResetEvent)
+    #[default]
+    Manual,
+
+    /// Release one waiter per set, then reset, like [`AutoResetEvent`](crate::AutoResetEvent)
+    Auto,
+
+    /// Release the threads currently waiting, without latching the set
+    Pulse
+}
+
+/// Boolean event whose latching behavior is chosen at runtime through [`EventBuilder`].
+///
+/// # Examples
+///
+/// ```rust
+/// # use std::time::Duration;
+/// use sync_wait_object::{Event, EventMode, SignalWaitable};
+///
+/// let mut ev = Event::builder().mode(EventMode::Auto).initial(true).name("job-ready").build();
+/// assert_eq!(ev.name(), Some("job-ready"));
+///
+/// assert!(ev.wait(Duration::from_millis(10)).is_ok());
+/// assert!(ev.wait(Duration::from_millis(10)).is_err());
+///
+/// ev.set().unwrap();
+/// assert!(ev.wait(Duration::from_millis(10)).is_ok());
+/// ```
+#[derive(Clone)]
+pub struct Event {
+    inner: WaitEvent<EventState>,
+    mode: EventMode
+}
+
+/// Builder of [`Event`].
+#[derive(Debug, Clone, Default)]
+pub struct EventBuilder {
+    mode: EventMode,
+    initial: bool,
+    name: Option<String>
+}
+
+struct EventState {
+    set: bool,
+    pulses: u64
+}
+
+// ------------------------------ IMPLEMENTATIONS ------------------------------
+impl Event {
+    #[inline]
+    pub fn builder() -> EventBuilder { EventBuilder::default() }
+
+    #[inline]
+    pub fn mode(&self) -> EventMode { self.mode }
+
+    #[inline]
+    pub fn name(&self) -> Option<&str> { self.inner.name() }
+
+    fn wait_event(&self, timeout: Option<Duration>) -> Result<()> {
+        match self.mode {
+            EventMode::Manual => self.inner.wait(timeout, |s| s.set).map(|_| ()),
+            EventMode::Auto => self.inner.wait_then(timeout, |s| s.set, |s| s.set = false),
+            EventMode::Pulse => {
+                let mut start = None;
+                self.inner.wait(timeout, |s| *start.get_or_insert(s.pulses) != s.pulses).map(|_| ())
+            }
+        }
+    }
+}
+
+impl SignalWaitable for Event {
+    #[inline] fn wait_until_set(&self) -> Result<()> { self.wait_event(None) }
+    #[inline] fn wait(&self, timeout: Duration) -> Result<()> { self.wait_event(Some(timeout)) }
+
+    fn set(&mut self) -> Result<()> {
+        let mode = self.mode;
+        self.inner.modify(|s| match mode {
+            EventMode::Pulse => s.pulses = s.pulses.wrapping_add(1),
+            _ => s.set = true
+        })
+    }
+
+    fn reset(&mut self) -> Result<()> {
+        self.inner.modify(|s| s.set = false)
+    }
+
+    /// Always `false` in [`EventMode::Pulse`], which never latches.
+    fn is_set(&self) -> Result<bool> {
+        Ok(self.inner.value()?.set)
+    }
+}
+
+impl EventBuilder {
+    #[inline]
+    pub fn mode(mut self, mode: EventMode) -> Self {
+        self.mode = mode;
+        self
+    }
+
+    /// Initial state of the event. Ignored in [`EventMode::Pulse`], which never latches.
+    #[inline]
+    pub fn initial(mut self, initial: bool) -> Self {
+        self.initial = initial;
+        self
+    }
+
+    #[inline]
+    pub fn name(mut self, name: impl Into<String>) -> Self {
+        self.name = Some(name.into());
+        self
+    }
+
+    pub fn build(self) -> Event {
+        let set = self.initial && self.mode != EventMode::Pulse;
+        let builder = WaitEvent::builder();
+        let builder = match self.name {
+            Some(name) => builder.name(name),
+            None => builder
+        };
+        Event { inner: builder.build(EventState { set, pulses: 0 }), mode: self.mode }
+    }
+}
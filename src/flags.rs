@@ -0,0 +1,97 @@
+use std::time::Duration;
+use crate::{ WaitEvent, Result };
+
+// ------------------------------ DATA TYPES ----------------------------------
+/// How [`EventFlags::wait_bits`] matches the requested mask.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WaitMode {
+    /// Any bit of the mask is set
+    Any,
+
+    /// All bits of the mask are set
+    All
+}
+
+/// Group of 32 event bits in one word (FreeRTOS-style event group), backed by `WaitEvent<u32>`.
+///
+/// # Examples
+///
+/// ```rust
+/// # use std::{thread, time::Duration};
+/// use sync_wait_object::{EventFlags, WaitMode};
+///
+/// const NET_UP: u32 = 0b01;
+/// const DISK_UP: u32 = 0b10;
+///
+/// let flags = EventFlags::new();
+/// let mut setter = flags.clone();
+///
+/// thread::spawn(move || {
+///     setter.set_bits(NET_UP).unwrap();
+///     setter.set_bits(DISK_UP).unwrap();
+/// });
+///
+/// let bits = flags.wait_bits(NET_UP | DISK_UP, WaitMode::All, true, Some(Duration::from_secs(1))).unwrap();
+/// assert_eq!(bits, NET_UP | DISK_UP);
+/// assert_eq!(flags.bits().unwrap(), 0);
+/// ```
+#[derive(Clone)]
+pub struct EventFlags(WaitEvent<u32>);
+
+// ------------------------------ IMPLEMENTATIONS ------------------------------
+impl EventFlags {
+    #[inline]
+    pub fn new() -> Self { Self::new_init(0) }
+
+    #[inline]
+    pub fn new_init(initial_bits: u32) -> Self { Self(WaitEvent::new_init(initial_bits)) }
+
+    /// Current bits.
+    pub fn bits(&self) -> Result<u32> {
+        self.0.value().map(|v| *v)
+    }
+
+    /// Set the bits of `mask`, and return the bits after the change.
+    pub fn set_bits(&mut self, mask: u32) -> Result<u32> {
+        self.0.modify(|v| { *v |= mask; *v })
+    }
+
+    /// Clear the bits of `mask`, and return the bits before the change.
+    pub fn clear_bits(&mut self, mask: u32) -> Result<u32> {
+        self.0.modify(|v| { let before = *v; *v &= !mask; before })
+    }
+
+    /// Wait until the bits of `mask` match according to `mode`, or timed-out from `timeout`. Returns the bits observed
+    /// when the wait ends.
+    ///
+    /// # Arguments
+    ///
+    /// * `mask` - Bits to wait for
+    /// * `mode` - Whether any or all bits of `mask` are required
+    /// * `clear_on_exit` - Clear the bits of `mask` when the wait succeeds
+    /// * `timeout` - Maximum wait time
+    pub fn wait_bits(&self, mask: u32, mode: WaitMode, clear_on_exit: bool, timeout: Option<Duration>) -> Result<u32> {
+        let matches = |v: &u32| match mode {
+            WaitMode::Any => v & mask != 0,
+            WaitMode::All => v & mask == mask
+        };
+        if !clear_on_exit { return self.0.wait(timeout, matches).map(|v| *v) }
+        self.0.wait_then(timeout, matches, |v| { let bits = *v; *v &= !mask; bits })
+    }
+}
+
+impl Default for EventFlags {
+    #[inline] fn default() -> Self { Self::new() }
+}
+
+impl From<WaitEvent<u32>> for EventFlags {
+    fn from(value: WaitEvent<u32>) -> Self {
+        Self(value)
+    }
+}
+
+impl From<EventFlags> for WaitEvent<u32> {
+    fn from(value: EventFlags) -> Self {
+        value.0
+    }
+}
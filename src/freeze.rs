@@ -0,0 +1,107 @@
+use std::sync::atomic::Ordering;
+use crate::{ EventBackend, WaitEvent, Result, StdBackend };
+
+// ------------------------------ DATA TYPES ----------------------------------
+/// Freeze from [`WaitEvent::freeze`], which keeps all setters of the event blocked until it is
+/// [`unfreeze`](FreezeGuard::unfreeze)d or dropped.
+#[must_use = "the event unfreezes as soon as the guard drops"]
+pub struct FreezeGuard<'a, T, B: EventBackend<T> = StdBackend<T>> {
+    event: &'a WaitEvent<T, B>
+}
+
+// ------------------------------ IMPLEMENTATIONS ------------------------------
+impl<T, B: EventBackend<T>> WaitEvent<T, B> {
+    /// Block every change of the state until the returned guard is released, while readers and waiters go on. Freezing
+    /// several events, then reading them, gives a consistent checkpoint. Setters blocked in the meantime resume, and
+    /// notify, once the last freeze of the event is released.
+    ///
+    /// A change in progress completes before this returns. Changing the state from the freezing thread deadlocks.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use std::{thread, time::Duration};
+    /// use sync_wait_object::WaitEvent;
+    ///
+    /// let balance_a = WaitEvent::new_init(100);
+    /// let balance_b = WaitEvent::new_init(0);
+    ///
+    /// let (mut a, mut b) = (balance_a.clone(), balance_b.clone());
+    /// let (freeze_a, freeze_b) = (balance_a.freeze().unwrap(), balance_b.freeze().unwrap());
+    /// let transfer = thread::spawn(move || {
+    ///     a.set_state(50).unwrap();
+    ///     b.set_state(50).unwrap();
+    /// });
+    ///
+    /// thread::sleep(Duration::from_millis(20));
+    /// assert_eq!(*balance_a.value().unwrap() + *balance_b.value().unwrap(), 100);
+    ///
+    /// freeze_a.unfreeze();
+    /// freeze_b.unfreeze();
+    /// transfer.join().unwrap();
+    /// assert_eq!(*balance_b.value().unwrap(), 50);
+    /// ```
+    ///
+    /// Changes made by waiters once their wait ends, and by [`EventGroup`](crate::EventGroup), are blocked too:
+    ///
+    /// ```rust
+    /// # use std::{thread, time::Duration};
+    /// use sync_wait_object::{EventGroup, ManualResetEvent, WaitEvent};
+    ///
+    /// type Change = fn(&WaitEvent<Option<u8>>);
+    /// let changes: [Change; 4] = [
+    ///     |ev| ev.wait_then(None, Option::is_some, |v| *v = Some(2)).unwrap(),
+    ///     |ev| drop(ev.wait_reset(None, || Some(2), Option::is_some).unwrap()),
+    ///     |ev| drop(ev.wait_reset_with(None, Option::is_some, |_| Some(2)).unwrap()),
+    ///     |ev| drop(ev.wait_take(None).unwrap())
+    /// ];
+    /// for change in changes {
+    ///     let ev = WaitEvent::new_init(Some(1));
+    ///     let frozen = ev.freeze().unwrap();
+    ///     let changer = ev.clone();
+    ///     let changer = thread::spawn(move || change(&changer));
+    ///
+    ///     thread::sleep(Duration::from_millis(20));
+    ///     assert_eq!(*ev.value().unwrap(), Some(1));
+    ///
+    ///     frozen.unfreeze();
+    ///     changer.join().unwrap();
+    ///     assert_ne!(*ev.value().unwrap(), Some(1));
+    /// }
+    ///
+    /// let ready = WaitEvent::new_init(false);
+    /// let frozen = ready.freeze().unwrap();
+    /// let mut setter = ManualResetEvent::from(ready.clone());
+    /// let set = thread::spawn(move || EventGroup::set_all(&[&mut setter]).unwrap());
+    ///
+    /// thread::sleep(Duration::from_millis(20));
+    /// assert!(!*ready.value().unwrap());
+    ///
+    /// frozen.unfreeze();
+    /// set.join().unwrap();
+    /// assert!(*ready.value().unwrap());
+    /// ```
+    pub fn freeze(&self) -> Result<FreezeGuard<'_, T, B>> {
+        let state = self.lock_state()?;
+        self.0.frozen.fetch_add(1, Ordering::SeqCst);
+        drop(state);
+        Ok(FreezeGuard { event: self })
+    }
+}
+
+impl<T, B: EventBackend<T>> FreezeGuard<'_, T, B> {
+    /// Release the freeze. Same as dropping the guard.
+    #[inline]
+    pub fn unfreeze(self) {}
+}
+
+impl<T, B: EventBackend<T>> Drop for FreezeGuard<'_, T, B> {
+    fn drop(&mut self) {
+        // the count changes under the state lock, so no blocked setter misses the wake-up
+        let state = self.event.lock_state();
+        if self.event.0.frozen.fetch_sub(1, Ordering::SeqCst) == 1 {
+            self.event.0.backend.notify_all();
+        }
+        drop(state);
+    }
+}
@@ -0,0 +1,120 @@
+use std::{ sync::MutexGuard, time::Duration };
+use crate::{ chain, CancellationToken, Deadline, WaitEvent, ManualResetEvent, WaitObjectError, Result };
+
+// ------------------------------ DATA TYPES ----------------------------------
+/// Operations over a group of [`ManualResetEvent`]s which must be observed atomically. All involved locks are taken in
+/// a canonical order, so concurrent group operations never deadlock.
+///
+/// # Examples
+///
+/// ```rust
+/// # use std::{thread, time::Duration};
+/// use sync_wait_object::{EventGroup, ManualResetEvent};
+///
+/// let mut config_ready = ManualResetEvent::new();
+/// let mut cache_ready = ManualResetEvent::new();
+/// let (a, b) = (config_ready.clone(), cache_ready.clone());
+///
+/// thread::spawn(move || EventGroup::set_all(&[&mut config_ready, &mut cache_ready]).unwrap());
+///
+/// EventGroup::wait_all(&[&a, &b], Some(Duration::from_secs(1))).unwrap();
+/// ```
+pub struct EventGroup;
+
+// ------------------------------ IMPLEMENTATIONS ------------------------------
+impl EventGroup {
+    /// Set all `events` at once. Holds and freezes of the events are waited out first.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use std::{thread, time::Duration};
+    /// use sync_wait_object::{EventGroup, ManualResetEvent, WaitEvent};
+    ///
+    /// let ready = WaitEvent::new_init(false);
+    /// let hold = ready.wait_and_hold(None, Duration::from_secs(5), |_| true).unwrap();
+    /// let mut setter = ManualResetEvent::from(ready.clone());
+    /// let set = thread::spawn(move || EventGroup::set_all(&[&mut setter]).unwrap());
+    ///
+    /// thread::sleep(Duration::from_millis(20));
+    /// assert!(!*ready.value().unwrap());
+    ///
+    /// drop(hold);
+    /// set.join().unwrap();
+    /// assert!(*ready.value().unwrap());
+    /// ```
+    pub fn set_all(events: &[&mut ManualResetEvent]) -> Result<()> {
+        Self::store_all(events, true)
+    }
+
+    /// Reset all `events` at once.
+    pub fn reset_all(events: &[&mut ManualResetEvent]) -> Result<()> {
+        Self::store_all(events, false)
+    }
+
+    /// Wait until all `events` are observed set at the same time, or timed-out from `timeout` (`None` for the default
+    /// timeout, see [`WaitPolicy`](crate::WaitPolicy)).
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use std::time::Duration;
+    /// use sync_wait_object::{EventGroup, ManualResetEvent, WaitPolicy, WaitObjectError};
+    ///
+    /// let (a, b) = (ManualResetEvent::new_init(true), ManualResetEvent::new());
+    /// let policy = WaitPolicy { default_timeout: Some(Duration::from_millis(10)), ..WaitPolicy::DEFAULT };
+    ///
+    /// let r = WaitPolicy::scope(policy, || EventGroup::wait_all(&[&a, &b], None));
+    /// assert_eq!(r, Err(WaitObjectError::Timeout));
+    /// ```
+    pub fn wait_all(events: &[&ManualResetEvent], timeout: Option<Duration>) -> Result<()> {
+        let events: Vec<_> = events.iter().map(|e| &e.0).collect();
+        let timeout = timeout.or_else(|| WaitEvent::unset_timeout_of(&events));
+        let waiter = WaitEvent::<bool>::create_waiter(Deadline::limit(timeout)?);
+        loop {
+            let unset = lock_all(&events)?.into_iter().find(|(_, state)| !**state).map(|(e, _)| e);
+            match unset {
+                None => return Ok(()),
+                Some(_) if !waiter() => return Err(WaitObjectError::Timeout),
+                Some(_) if CancellationToken::scope_cancelled() => return Err(WaitObjectError::Cancelled),
+                Some(e) => e.park_slice()?
+            }
+        }
+    }
+
+    fn store_all(events: &[&mut ManualResetEvent], value: bool) -> Result<()> {
+        let events: Vec<_> = events.iter().map(|e| &e.0).collect();
+        let mut locked = lock_all(&events)?;
+        // wait out holds and freezes one event at a time, without keeping the other locks meanwhile
+        while let Some(held) = first_held(&locked)? {
+            drop(locked);
+            drop(held.lock_for_update()?);
+            locked = lock_all(&events)?;
+        }
+        for (_, state) in locked.iter_mut() {
+            **state = value;
+        }
+        for (event, state) in locked.iter() {
+            event.0.notify(state);
+        }
+        drop(locked);
+        chain::propagate_deferred();
+        Ok(())
+    }
+}
+
+/// First of the `locked` events which is held or frozen, if any.
+fn first_held<'a, T>(locked: &[(&'a WaitEvent<T>, MutexGuard<'a, T>)]) -> Result<Option<&'a WaitEvent<T>>> {
+    for (event, _) in locked {
+        if event.is_held()? { return Ok(Some(event)) }
+    }
+    Ok(None)
+}
+
+/// Lock all distinct `events` in the order of their identity.
+pub(crate) fn lock_all<'a, T>(events: &[&'a WaitEvent<T>]) -> Result<Vec<(&'a WaitEvent<T>, MutexGuard<'a, T>)>> {
+    let mut ordered = events.to_vec();
+    ordered.sort_by_key(|e| e.id());
+    ordered.dedup_by_key(|e| e.id());
+    ordered.into_iter().map(|e| Ok((e, e.lock_state()?))).collect()
+}
@@ -0,0 +1,80 @@
+use std::{ sync::{ Arc, Mutex }, time::Duration };
+use crate::{ WaitEvent, Result };
+
+// ------------------------------ DATA TYPES ----------------------------------
+/// Request/response protocol over a pair of events with typed payload slots. Clients [`call`](Handshake::call) with a
+/// request and wait for its response, a server [`serve`](Handshake::serve)s one request at a time.
+///
+/// Calls are serialized, and each request is tagged, so a response arriving after its caller timed out is never
+/// mistaken for the response of a later call. Only in-process use is supported.
+///
+/// # Examples
+///
+/// ```rust
+/// # use std::{thread, time::Duration};
+/// use sync_wait_object::Handshake;
+///
+/// let rpc = Handshake::new();
+/// let server = rpc.clone();
+/// thread::spawn(move || while server.serve(None, |n: u32| n * 2).is_ok() {});
+///
+/// assert_eq!(rpc.call(21, Some(Duration::from_secs(1))).unwrap(), 42);
+/// ```
+pub struct Handshake<Req, Resp> {
+    request: WaitEvent<Option<(u64, Req)>>,
+    response: WaitEvent<Option<(u64, Resp)>>,
+    sequence: Arc<Mutex<u64>>
+}
+
+// ------------------------------ IMPLEMENTATIONS ------------------------------
+impl<Req, Resp> Handshake<Req, Resp> {
+    pub fn new() -> Self {
+        Self { request: WaitEvent::new_init(None), response: WaitEvent::new_init(None), sequence: Arc::new(Mutex::new(0)) }
+    }
+
+    /// Send `payload` to the server and wait for its response. If the wait times out, a request not yet taken by the
+    /// server is withdrawn.
+    ///
+    /// # Arguments
+    ///
+    /// * `payload` - Request payload
+    /// * `timeout` - Maximum wait time for the response
+    pub fn call(&self, payload: Req, timeout: Option<Duration>) -> Result<Resp> {
+        let mut sequence = self.sequence.lock()?;
+        *sequence = sequence.wrapping_add(1);
+        let id = *sequence;
+
+        // the response slot is cleared before the request is published, so no stale response can satisfy this call
+        self.response.modify_quiet(|r| *r = None)?;
+        self.request.modify(|r| *r = Some((id, payload)))?;
+
+        let result = self.response.wait_then(timeout, |r| matches!(r, Some((rid, _)) if *rid == id), |r| r.take().unwrap().1);
+        if result.is_err() {
+            self.request.modify_quiet(|r| if matches!(r, Some((rid, _)) if *rid == id) { *r = None })?;
+        }
+        result
+    }
+
+    /// Wait for a request, run `handler` on it outside of any lock, and send back its response.
+    ///
+    /// # Arguments
+    ///
+    /// * `timeout` - Maximum wait time for a request
+    /// * `handler` - Function computing the response
+    pub fn serve(&self, timeout: Option<Duration>, handler: impl FnOnce(Req) -> Resp) -> Result<()> {
+        let (id, payload) = self.request.wait_then(timeout, Option::is_some, |r| r.take().unwrap())?;
+        let response = handler(payload);
+        self.response.modify(|r| *r = Some((id, response)))
+    }
+}
+
+impl<Req, Resp> Clone for Handshake<Req, Resp> {
+    fn clone(&self) -> Self {
+        Self { request: self.request.clone(), response: self.response.clone(), sequence: self.sequence.clone() }
+    }
+}
+
+impl<Req, Resp> Default for Handshake<Req, Resp> {
+    #[inline]
+    fn default() -> Self { Self::new() }
+}
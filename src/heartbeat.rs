@@ -0,0 +1,61 @@
+use std::time::{ Duration, Instant };
+use crate::{ WaitEvent, Result, timer::TimerCheck };
+
+// ------------------------------ DATA TYPES ----------------------------------
+/// Heartbeat where a producer [`beat`](Heartbeat::beat)s and consumers are woken when no beat arrived for a given gap.
+///
+/// # Examples
+///
+/// ```rust
+/// # use std::{thread, time::Duration};
+/// use sync_wait_object::Heartbeat;
+///
+/// let heartbeat = Heartbeat::new();
+/// let producer = heartbeat.clone();
+///
+/// thread::spawn(move || for _ in 0..5 {
+///     producer.beat().unwrap();
+///     thread::sleep(Duration::from_millis(10));
+/// });
+///
+/// let silence = heartbeat.wait_missed(Duration::from_millis(100), Some(Duration::from_secs(2))).unwrap();
+/// assert!(silence >= Duration::from_millis(100));
+/// ```
+#[derive(Clone)]
+pub struct Heartbeat(WaitEvent<Instant>);
+
+// ------------------------------ IMPLEMENTATIONS ------------------------------
+impl Heartbeat {
+    /// Create a heartbeat whose first beat is now.
+    #[inline]
+    pub fn new() -> Self { Self(WaitEvent::new_init(Instant::now())) }
+
+    /// Record a beat.
+    pub fn beat(&self) -> Result<()> {
+        self.0.modify(|last| *last = Instant::now())
+    }
+
+    /// Time of the last beat.
+    pub fn last_beat(&self) -> Result<Instant> {
+        self.0.value().map(|last| *last)
+    }
+
+    /// Wait until no beat arrived for `gap`, or timed-out from `timeout`. Returns the time elapsed since the last beat.
+    ///
+    /// # Arguments
+    ///
+    /// * `gap` - Silence which counts as a missed heartbeat
+    /// * `timeout` - Maximum wait time
+    pub fn wait_missed(&self, gap: Duration, timeout: Option<Duration>) -> Result<Duration> {
+        let mut silence = Duration::ZERO;
+        drop(self.0.wait_timer(timeout, |last, now| {
+            silence = now - *last;
+            if silence >= gap { TimerCheck::Ready } else { TimerCheck::Pending(Some(*last + gap)) }
+        })?);
+        Ok(silence)
+    }
+}
+
+impl Default for Heartbeat {
+    #[inline] fn default() -> Self { Self::new() }
+}
@@ -0,0 +1,155 @@
+use std::{ sync::atomic::Ordering, time::{ Duration, Instant } };
+use crate::{ timer::TimerCheck, Deadline, EventBackend, WaitEvent, Result, StdBackend };
+
+// ------------------------------ DATA TYPES ----------------------------------
+/// Hold from [`WaitEvent::wait_and_hold`], which keeps setters of the event blocked until the hold window ends or the
+/// guard drops.
+#[must_use = "the hold ends as soon as the guard drops"]
+pub struct HoldGuard<'a, T, B: EventBackend<T> = StdBackend<T>> {
+    event: &'a WaitEvent<T, B>,
+    until: Instant
+}
+
+// ------------------------------ IMPLEMENTATIONS ------------------------------
+impl<T, B: EventBackend<T>> WaitEvent<T, B> {
+    /// Wait until the `checker` returns true, or timed-out from `timeout`, then keep the state unchanged for `hold`:
+    /// setters block until the window ends or the returned guard drops. Unlike the guard of [`wait`](Self::wait), the
+    /// state lock is not held, so other waiters and readers are not blocked during the hold.
+    ///
+    /// # Arguments
+    ///
+    /// * `timeout` - Maximum wait time
+    /// * `hold` - Maximum time the state is kept unchanged
+    /// * `checker` - Checker function, once it returns `true`, the wait ends
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use std::{thread, time::Duration};
+    /// use sync_wait_object::WaitEvent;
+    ///
+    /// let config = WaitEvent::new_init("v1");
+    /// let hold = config.wait_and_hold(None, Duration::from_secs(1), |_| true).unwrap();
+    ///
+    /// let mut updater = config.clone();
+    /// let update = thread::spawn(move || updater.set_state("v2").unwrap());
+    ///
+    /// thread::sleep(Duration::from_millis(20));
+    /// assert_eq!(*config.value().unwrap(), "v1");
+    ///
+    /// drop(hold);
+    /// update.join().unwrap();
+    /// assert_eq!(*config.value().unwrap(), "v2");
+    /// ```
+    ///
+    /// Waiters which change the state once their wait ends wait out the hold too:
+    ///
+    /// ```rust
+    /// # use std::{thread, time::Duration};
+    /// use sync_wait_object::WaitEvent;
+    ///
+    /// type Change = fn(&WaitEvent<Option<u8>>);
+    /// let changes: [Change; 4] = [
+    ///     |ev| ev.wait_then(None, Option::is_some, |v| *v = Some(2)).unwrap(),
+    ///     |ev| drop(ev.wait_reset(None, || Some(2), Option::is_some).unwrap()),
+    ///     |ev| drop(ev.wait_reset_with(None, Option::is_some, |_| Some(2)).unwrap()),
+    ///     |ev| drop(ev.wait_take(None).unwrap())
+    /// ];
+    /// for change in changes {
+    ///     let ev = WaitEvent::new_init(Some(1));
+    ///     let hold = ev.wait_and_hold(None, Duration::from_secs(5), |_| true).unwrap();
+    ///     let changer = ev.clone();
+    ///     let changer = thread::spawn(move || change(&changer));
+    ///
+    ///     thread::sleep(Duration::from_millis(20));
+    ///     assert_eq!(*ev.value().unwrap(), Some(1));
+    ///
+    ///     drop(hold);
+    ///     changer.join().unwrap();
+    ///     assert_ne!(*ev.value().unwrap(), Some(1));
+    /// }
+    /// ```
+    pub fn wait_and_hold(&self, timeout: Option<Duration>, hold: Duration, checker: impl FnMut(&T) -> bool) -> Result<HoldGuard<'_, T, B>> {
+        let state = self.wait(timeout, checker)?;
+        let until = Instant::now().checked_add(hold).unwrap_or_else(|| Instant::now() + Duration::from_secs(u32::MAX as u64));
+        let mut held = self.0.held_until.lock()?;
+        *held = Some(held.map_or(until, |h| h.max(until)));
+        drop(state);
+        Ok(HoldGuard { event: self, until })
+    }
+
+    /// Lock the state for a change, waiting out any hold from [`wait_and_hold`](Self::wait_and_hold) or
+    /// [`freeze`](Self::freeze).
+    pub(crate) fn lock_for_update(&self) -> Result<B::Guard<'_>> {
+        self.wait_out_holds(self.lock_state()?).map(|(state, _)| state)
+    }
+
+    /// Same as [`wait_with_waiter`](Self::wait_with_waiter), for a waiter which changes the state afterwards: the wait
+    /// also outlasts any hold or freeze, and the `checker` is re-evaluated after one.
+    pub(crate) fn wait_for_update(&self, timeout: Option<Duration>, mut checker: impl FnMut(&T) -> bool) -> Result<B::Guard<'_>> {
+        let deadline = Deadline::from_timeout(Deadline::limit(timeout)?);
+        let mut state = self.wait_with_waiter(timeout, &mut checker)?;
+        loop {
+            let (held_state, blocked) = self.wait_out_holds(state)?;
+            if !blocked || checker(&*held_state) { return Ok(held_state) }
+            drop(held_state);
+            state = self.wait_with_waiter(deadline.timeout()?, &mut checker)?;
+        }
+    }
+
+    /// Same as [`wait_timer`](Self::wait_timer), for a waiter which changes the state afterwards, like
+    /// [`wait_for_update`](Self::wait_for_update).
+    pub(crate) fn wait_timer_for_update(&self, timeout: Option<Duration>, mut checker: impl FnMut(&T, Instant) -> TimerCheck) -> Result<B::Guard<'_>> {
+        let deadline = Deadline::from_timeout(Deadline::limit(timeout)?);
+        let mut state = self.wait_timer(timeout, &mut checker)?;
+        loop {
+            let (held_state, blocked) = self.wait_out_holds(state)?;
+            if !blocked || matches!(checker(&*held_state, Instant::now()), TimerCheck::Ready) { return Ok(held_state) }
+            drop(held_state);
+            state = self.wait_timer(deadline.timeout()?, &mut checker)?;
+        }
+    }
+
+    /// Whether setters are currently blocked by a hold or a freeze. The state lock must be held.
+    pub(crate) fn is_held(&self) -> Result<bool> {
+        if self.0.frozen.load(Ordering::SeqCst) > 0 { return Ok(true) }
+        Ok(self.0.held_until.lock()?.is_some_and(|until| Instant::now() < until))
+    }
+
+    /// Block on the locked `state` until no hold or freeze is left. Also returns whether it blocked at all.
+    fn wait_out_holds<'a>(&'a self, mut state: B::Guard<'a>) -> Result<(B::Guard<'a>, bool)> {
+        let mut blocked = false;
+        loop {
+            if self.0.frozen.load(Ordering::SeqCst) > 0 {
+                state = self.block_setter(state, None)?.0;
+            } else {
+                let held = *self.0.held_until.lock()?;
+                match held {
+                    Some(until) if Instant::now() < until => state = self.block_setter(state, Some(until))?.0,
+                    _ => return Ok((state, blocked))
+                }
+            }
+            blocked = true;
+        }
+    }
+}
+
+impl<T, B: EventBackend<T>> HoldGuard<'_, T, B> {
+    /// End of the hold window.
+    #[inline]
+    pub fn until(&self) -> Instant { self.until }
+}
+
+impl<T, B: EventBackend<T>> Drop for HoldGuard<'_, T, B> {
+    fn drop(&mut self) {
+        let Ok(state) = self.event.lock_state() else { return };
+        let mut held = self.event.0.held_until.lock().unwrap_or_else(|e| e.into_inner());
+        // a longer hold taken by another waiter stays in place
+        if *held == Some(self.until) {
+            *held = None;
+            drop(held);
+            self.event.0.backend.notify_all();
+        }
+        drop(state);
+    }
+}
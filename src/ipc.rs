@@ -0,0 +1,361 @@
+//! Cross-process named events.
+//!
+//! [`NamedEvent`] maps to a Win32 named event on Windows, and to a POSIX named semaphore on Unix, with the same
+//! manual-reset and auto-reset semantics on both. Processes share an event by using the same name.
+//!
+//! # Semantics
+//!
+//! Both platforms guarantee, for an auto-reset event:
+//!
+//! * each set releases exactly one waiter, and the event is reset when it does;
+//! * sets do not accumulate: setting an already set event has no effect;
+//! * [`is_set`](crate::SignalWaitable::is_set) does not consume the signal.
+//!
+//! A manual-reset event releases every waiter and stays set until reset.
+//!
+//! The scenarios below run on every platform as a conformance check of the native backend.
+//!
+//! ```rust
+//! # use std::{thread, time::Duration};
+//! use sync_wait_object::{EventMode, SignalWaitable, ipc::NamedEvent};
+//!
+//! let name = format!("swo-auto-{}", std::process::id());
+//! let mut ev = NamedEvent::create(&name, EventMode::Auto, false).unwrap();
+//! let short = Duration::from_millis(20);
+//!
+//! // sets do not accumulate, and is_set does not consume
+//! ev.set().unwrap();
+//! ev.set().unwrap();
+//! assert!(ev.is_set().unwrap());
+//! assert!(ev.wait(short).is_ok());
+//! assert!(ev.wait(short).is_err());
+//!
+//! // one set releases exactly one of the blocked waiters
+//! let waiters: Vec<_> = (0..2).map(|_| {
+//!     let name = name.clone();
+//!     thread::spawn(move || NamedEvent::open(&name, EventMode::Auto).unwrap().wait(Duration::from_millis(300)).is_ok())
+//! }).collect();
+//! thread::sleep(Duration::from_millis(50));
+//! ev.set().unwrap();
+//! let released = waiters.into_iter().map(|w| w.join().unwrap()).filter(|ok| *ok).count();
+//! assert_eq!(released, 1);
+//! assert!(!ev.is_set().unwrap());
+//!
+//! // concurrent setters do not accumulate either
+//! let setters: Vec<_> = (0..4).map(|_| {
+//!     let mut setter = NamedEvent::open(&name, EventMode::Auto).unwrap();
+//!     thread::spawn(move || for _ in 0..1000 { setter.set().unwrap() })
+//! }).collect();
+//! setters.into_iter().for_each(|s| s.join().unwrap());
+//! assert!(ev.wait(short).is_ok());
+//! assert!(ev.wait(short).is_err());
+//!
+//! NamedEvent::unlink(&name).unwrap();
+//! ```
+//!
+//! ```rust
+//! # use std::time::Duration;
+//! use sync_wait_object::{EventMode, SignalWaitable, ipc::NamedEvent};
+//!
+//! let name = format!("swo-manual-{}", std::process::id());
+//! let mut ev = NamedEvent::create(&name, EventMode::Manual, true).unwrap();
+//! let short = Duration::from_millis(20);
+//!
+//! // a manual-reset event stays set for every waiter until reset
+//! assert!(ev.wait(short).is_ok());
+//! assert!(ev.wait(short).is_ok());
+//! ev.reset().unwrap();
+//! assert!(ev.wait(short).is_err());
+//!
+//! NamedEvent::unlink(&name).unwrap();
+//! ```
+
+use std::{ ffi::CString, time::Duration };
+use crate::{ EventMode, Result, SignalWaitable, WaitObjectError };
+
+// ------------------------------ DATA TYPES ----------------------------------
+/// Event shared between processes by name.
+///
+/// On Unix, the event is a pair of semaphores (`name` and `name.lock`), which outlive the processes using them until
+/// they are [`unlink`](NamedEvent::unlink)ed. A set racing with a reset from another process may leave a manual-reset
+/// event in either state.
+///
+/// # Examples
+///
+/// ```rust
+/// # use std::time::Duration;
+/// use sync_wait_object::{EventMode, SignalWaitable, ipc::NamedEvent};
+///
+/// let name = format!("swo-doc-{}", std::process::id());
+/// let waiter = NamedEvent::create(&name, EventMode::Manual, false).unwrap();
+///
+/// // usually in another process
+/// let mut setter = NamedEvent::open(&name, EventMode::Manual).unwrap();
+/// setter.set().unwrap();
+///
+/// assert!(waiter.wait(Duration::from_secs(1)).is_ok());
+/// assert!(waiter.is_set().unwrap());
+/// NamedEvent::unlink(&name).unwrap();
+/// ```
+pub struct NamedEvent {
+    inner: imp::Event,
+    mode: EventMode
+}
+
+// ------------------------------ FUNCTIONS -------------------------------------
+fn c_name(name: &str) -> Result<CString> {
+    CString::new(imp::native_name(name)).map_err(|_| WaitObjectError::OsError(0, "event name contains a nul byte".to_string()))
+}
+
+fn check_mode(mode: EventMode) {
+    assert!(mode != EventMode::Pulse, "named events support only the manual-reset and auto-reset modes");
+}
+
+// ------------------------------ IMPLEMENTATIONS ------------------------------
+impl NamedEvent {
+    /// Create the event `name`, or open it if it already exists (in which case `initial_state` is ignored).
+    ///
+    /// # Panics
+    ///
+    /// Panics if `mode` is [`EventMode::Pulse`].
+    pub fn create(name: &str, mode: EventMode, initial_state: bool) -> Result<Self> {
+        check_mode(mode);
+        Ok(Self { inner: imp::Event::create(&c_name(name)?, mode, initial_state)?, mode })
+    }
+
+    /// Open the existing event `name`. `mode` must match the mode the event was created with.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `mode` is [`EventMode::Pulse`].
+    pub fn open(name: &str, mode: EventMode) -> Result<Self> {
+        check_mode(mode);
+        Ok(Self { inner: imp::Event::open(&c_name(name)?)?, mode })
+    }
+
+    /// Remove the name of the event, so later [`create`](Self::create) calls make a new event. Processes which already
+    /// opened the event keep using it. A no-op on Windows, where the event is removed with its last handle.
+    pub fn unlink(name: &str) -> Result<()> {
+        imp::unlink(&c_name(name)?)
+    }
+
+    #[inline]
+    pub fn mode(&self) -> EventMode { self.mode }
+}
+
+impl SignalWaitable for NamedEvent {
+    #[inline] fn wait_until_set(&self) -> Result<()> { self.inner.wait(None, self.mode) }
+    #[inline] fn wait(&self, timeout: Duration) -> Result<()> { self.inner.wait(Some(timeout), self.mode) }
+    #[inline] fn set(&mut self) -> Result<()> { self.inner.set() }
+    #[inline] fn reset(&mut self) -> Result<()> { self.inner.reset() }
+    #[inline] fn is_set(&self) -> Result<bool> { self.inner.is_set() }
+}
+
+#[cfg(unix)]
+mod imp {
+    use std::{ ffi::{ CStr, CString }, io, time::{ Duration, Instant } };
+    use crate::{ Deadline, EventMode, Result, WaitObjectError };
+
+    /// Named semaphore whose count is kept at 0 (reset) or 1 (set), and a second named semaphore (the name suffixed
+    /// by `.lock`) used as a lock, so that changes of the count from different processes do not interleave.
+    ///
+    /// A process which dies while it holds the lock (within a set, a reset or an `is_set`) leaves the event unusable
+    /// until it is unlinked.
+    pub(super) struct Event {
+        sem: Semaphore,
+        lock: Semaphore
+    }
+
+    struct Semaphore(*mut libc::sem_t);
+
+    /// Holds the lock semaphore of an [`Event`] until it drops.
+    struct LockGuard<'a>(&'a Semaphore);
+
+    // SAFETY: semaphores are meant to be used from any thread
+    unsafe impl Send for Semaphore {}
+    unsafe impl Sync for Semaphore {}
+
+    fn last_error() -> WaitObjectError {
+        let e = io::Error::last_os_error();
+        WaitObjectError::OsError(e.raw_os_error().unwrap_or_default() as isize, e.to_string())
+    }
+
+    pub(super) fn native_name(name: &str) -> String {
+        if name.starts_with('/') { name.to_string() } else { format!("/{name}") }
+    }
+
+    fn lock_name(name: &CStr) -> CString {
+        let mut lock = name.to_bytes().to_vec();
+        lock.extend_from_slice(b".lock");
+        // SAFETY: `name` has no nul byte, and neither has the suffix
+        unsafe { CString::from_vec_unchecked(lock) }
+    }
+
+    pub(super) fn unlink(name: &CStr) -> Result<()> {
+        if unsafe { libc::sem_unlink(name.as_ptr()) } != 0 { return Err(last_error()) }
+        // another process may have unlinked the lock already
+        let _ = unsafe { libc::sem_unlink(lock_name(name).as_ptr()) };
+        Ok(())
+    }
+
+    impl Event {
+        pub(super) fn create(name: &CStr, _mode: EventMode, initial_state: bool) -> Result<Self> {
+            Ok(Self { sem: Semaphore::open(name, libc::O_CREAT, initial_state as libc::c_uint)?, lock: Semaphore::open_lock(name)? })
+        }
+
+        pub(super) fn open(name: &CStr) -> Result<Self> {
+            Ok(Self { sem: Semaphore::open(name, 0, 0)?, lock: Semaphore::open_lock(name)? })
+        }
+
+        fn lock(&self) -> Result<LockGuard<'_>> {
+            self.lock.take(None)?;
+            Ok(LockGuard(&self.lock))
+        }
+
+        pub(super) fn wait(&self, timeout: Option<Duration>, mode: EventMode) -> Result<()> {
+            self.sem.take(Deadline::from_timeout(timeout).instant())?;
+            // a manual-reset event stays set for the other waiters
+            if mode == EventMode::Manual { self.set() } else { Ok(()) }
+        }
+
+        pub(super) fn set(&self) -> Result<()> {
+            let _lock = self.lock()?;
+            // taking the count first keeps it at 1 when the event is already set
+            self.sem.try_take()?;
+            self.sem.post()
+        }
+
+        pub(super) fn reset(&self) -> Result<()> {
+            let _lock = self.lock()?;
+            while self.sem.try_take()? {}
+            Ok(())
+        }
+
+        pub(super) fn is_set(&self) -> Result<bool> {
+            let _lock = self.lock()?;
+            let set = self.sem.try_take()?;
+            if set { self.sem.post()? }
+            Ok(set)
+        }
+    }
+
+    impl Semaphore {
+        fn open(name: &CStr, flags: libc::c_int, initial: libc::c_uint) -> Result<Self> {
+            let sem = unsafe { libc::sem_open(name.as_ptr(), flags, 0o600 as libc::c_uint, initial) };
+            if sem == libc::SEM_FAILED { Err(last_error()) } else { Ok(Self(sem)) }
+        }
+
+        /// Open the lock of event `name`, creating it unlocked if needed.
+        #[inline]
+        fn open_lock(name: &CStr) -> Result<Self> {
+            Self::open(&lock_name(name), libc::O_CREAT, 1)
+        }
+
+        /// Take the count down from 1 without blocking. Returns whether it was taken.
+        fn try_take(&self) -> Result<bool> {
+            loop {
+                if unsafe { libc::sem_trywait(self.0) } == 0 { return Ok(true) }
+                match io::Error::last_os_error().raw_os_error() {
+                    Some(libc::EAGAIN) => return Ok(false),
+                    Some(libc::EINTR) => continue,
+                    _ => return Err(last_error())
+                }
+            }
+        }
+
+        fn post(&self) -> Result<()> {
+            if unsafe { libc::sem_post(self.0) } == 0 { Ok(()) } else { Err(last_error()) }
+        }
+
+        #[cfg(any(target_os = "linux", target_os = "android", target_os = "freebsd"))]
+        fn take(&self, deadline: Option<Instant>) -> Result<()> {
+            let Some(deadline) = deadline else {
+                loop {
+                    if unsafe { libc::sem_wait(self.0) } == 0 { return Ok(()) }
+                    if io::Error::last_os_error().raw_os_error() != Some(libc::EINTR) { return Err(last_error()) }
+                }
+            };
+            loop {
+                // sem_timedwait takes an absolute CLOCK_REALTIME time
+                let mut now = libc::timespec { tv_sec: 0, tv_nsec: 0 };
+                unsafe { libc::clock_gettime(libc::CLOCK_REALTIME, &mut now) };
+                let Some(at) = Duration::new(now.tv_sec as u64, now.tv_nsec as u32).checked_add(deadline.saturating_duration_since(Instant::now()))
+                    else { return self.take(None) };
+                let at = libc::timespec { tv_sec: at.as_secs() as libc::time_t, tv_nsec: at.subsec_nanos() as _ };
+                if unsafe { libc::sem_timedwait(self.0, &at) } == 0 { return Ok(()) }
+                match io::Error::last_os_error().raw_os_error() {
+                    Some(libc::ETIMEDOUT) => return Err(WaitObjectError::Timeout),
+                    Some(libc::EINTR) => continue,
+                    _ => return Err(last_error())
+                }
+            }
+        }
+
+        /// Platforms without `sem_timedwait` poll the semaphore.
+        #[cfg(not(any(target_os = "linux", target_os = "android", target_os = "freebsd")))]
+        fn take(&self, deadline: Option<Instant>) -> Result<()> {
+            loop {
+                if self.try_take()? { return Ok(()) }
+                if deadline.is_some_and(|d| Instant::now() >= d) { return Err(WaitObjectError::Timeout) }
+                std::thread::sleep(crate::ANY_POLL_INTERVAL);
+            }
+        }
+    }
+
+    impl Drop for LockGuard<'_> {
+        fn drop(&mut self) {
+            let _ = self.0.post();
+        }
+    }
+
+    impl Drop for Semaphore {
+        fn drop(&mut self) {
+            unsafe { libc::sem_close(self.0); }
+        }
+    }
+}
+
+#[cfg(windows)]
+mod imp {
+    use std::{ ffi::CStr, time::Duration };
+    use windows::{
+        core::PCSTR,
+        Win32::System::Threading::{ CreateEventA, OpenEventA, EVENT_MODIFY_STATE, SYNCHRONIZATION_SYNCHRONIZE }
+    };
+    use crate::{ EventMode, Result, SignalWaitable, windows::{ WaitEvent, get_last_error } };
+
+    pub(super) struct Event(WaitEvent);
+
+    #[inline]
+    pub(super) fn native_name(name: &str) -> String { name.to_string() }
+
+    #[inline]
+    pub(super) fn unlink(_name: &CStr) -> Result<()> { Ok(()) }
+
+    impl Event {
+        pub(super) fn create(name: &CStr, mode: EventMode, initial_state: bool) -> Result<Self> {
+            let name = PCSTR(name.as_ptr() as *const u8);
+            unsafe { CreateEventA(None, mode == EventMode::Manual, initial_state, name) }
+                .map(|h| Self(WaitEvent(h)))
+                .map_err(|_| get_last_error())
+        }
+
+        pub(super) fn open(name: &CStr) -> Result<Self> {
+            let name = PCSTR(name.as_ptr() as *const u8);
+            unsafe { OpenEventA(EVENT_MODIFY_STATE | SYNCHRONIZATION_SYNCHRONIZE, false, name) }
+                .map(|h| Self(WaitEvent(h)))
+                .map_err(|_| get_last_error())
+        }
+
+        pub(super) fn wait(&self, timeout: Option<Duration>, _mode: EventMode) -> Result<()> {
+            match timeout {
+                Some(t) => self.0.wait(t),
+                None => self.0.wait_until_set()
+            }
+        }
+
+        #[inline] pub(super) fn set(&mut self) -> Result<()> { self.0.set() }
+        #[inline] pub(super) fn reset(&mut self) -> Result<()> { self.0.reset() }
+        #[inline] pub(super) fn is_set(&self) -> Result<bool> { self.0.is_set() }
+    }
+}
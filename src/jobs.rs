@@ -0,0 +1,72 @@
+use std::{ panic::{ self, AssertUnwindSafe }, thread, time::Duration };
+use crate::{ ManualResetEvent, SignalWaitable, WaitEvent, Result };
+
+// ------------------------------ DATA TYPES ----------------------------------
+/// Rayon job from [`spawn_waitable_in`], whose completion can be waited for with a timeout. Only available with the
+/// `rayon` feature.
+pub struct WaitableJob<T> {
+    done: ManualResetEvent,
+    result: WaitEvent<Option<thread::Result<T>>>
+}
+
+// ------------------------------ FUNCTIONS -------------------------------------
+/// Spawn `f` on the rayon thread `pool`. The event of the returned job is set when `f` finishes, by returning or
+/// panicking, so it can be combined with other wait objects.
+///
+/// # Examples
+///
+/// ```rust
+/// # use std::time::Duration;
+/// use sync_wait_object::{ManualResetEvent, spawn_waitable_in};
+///
+/// let pool = rayon::ThreadPoolBuilder::new().num_threads(2).build().unwrap();
+/// let a = spawn_waitable_in(&pool, || (1..=10).sum::<u32>());
+/// let b = spawn_waitable_in(&pool, || (11..=20).sum::<u32>());
+///
+/// ManualResetEvent::wait_quorum(&[a.event(), b.event()], 2, Some(Duration::from_secs(1))).unwrap();
+/// let total = a.wait(None).unwrap().unwrap() + b.wait(None).unwrap().unwrap();
+/// assert_eq!(total, 210);
+/// ```
+pub fn spawn_waitable_in<T, F>(pool: &rayon::ThreadPool, f: F) -> WaitableJob<T>
+where F: FnOnce() -> T + Send + 'static,
+      T: Send + 'static
+{
+    let (job, mut done, mut result) = WaitableJob::new();
+    pool.spawn(move || {
+        let _ = result.set_state(Some(panic::catch_unwind(AssertUnwindSafe(f))));
+        let _ = done.set();
+    });
+    job
+}
+
+/// Same as [`spawn_waitable_in`], on the global rayon thread pool.
+pub fn spawn_waitable<T, F>(f: F) -> WaitableJob<T>
+where F: FnOnce() -> T + Send + 'static,
+      T: Send + 'static
+{
+    let (job, mut done, mut result) = WaitableJob::new();
+    rayon::spawn(move || {
+        let _ = result.set_state(Some(panic::catch_unwind(AssertUnwindSafe(f))));
+        let _ = done.set();
+    });
+    job
+}
+
+// ------------------------------ IMPLEMENTATIONS ------------------------------
+impl<T> WaitableJob<T> {
+    fn new() -> (Self, ManualResetEvent, WaitEvent<Option<thread::Result<T>>>) {
+        let job = Self { done: ManualResetEvent::new(), result: WaitEvent::new_init(None) };
+        let (done, result) = (job.done.clone(), job.result.clone());
+        (job, done, result)
+    }
+
+    /// Event set when the job finishes.
+    #[inline]
+    pub fn event(&self) -> &ManualResetEvent { &self.done }
+
+    /// Wait until the job finishes, or timed-out from `timeout`, and take its result. `Err` of the inner result holds
+    /// the panic payload of the job.
+    pub fn wait(self, timeout: Option<Duration>) -> Result<thread::Result<T>> {
+        self.result.wait_then(timeout, Option::is_some, |r| r.take().expect("result is present once the job finishes"))
+    }
+}
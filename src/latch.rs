@@ -0,0 +1,76 @@
+use std::time::Duration;
+use crate::{ WaitEvent, Result };
+
+// ------------------------------ DATA TYPES ----------------------------------
+/// Event latching the first value set: later sets are rejected, and all waiters observe the first value forever. Models
+/// "first result wins" and "first error wins" races.
+///
+/// # Examples
+///
+/// ```rust
+/// # use std::{thread, time::Duration};
+/// use sync_wait_object::LatchValue;
+///
+/// let first_error = LatchValue::new();
+/// let mut a = first_error.clone();
+/// thread::spawn(move || a.set("disk full").unwrap()).join().unwrap();
+///
+/// let mut b = first_error.clone();
+/// assert_eq!(b.set("timeout").unwrap(), Err("timeout"));
+/// assert_eq!(first_error.wait(Some(Duration::from_secs(1))).unwrap(), "disk full");
+/// ```
+pub struct LatchValue<T>(WaitEvent<Option<T>>);
+
+// ------------------------------ IMPLEMENTATIONS ------------------------------
+impl<T> LatchValue<T> {
+    #[inline]
+    pub fn new() -> Self { Self(WaitEvent::new_init(None)) }
+
+    /// Latch `value` if no value is latched yet, waking all waiters. Otherwise, the value is returned back.
+    pub fn set(&mut self, value: T) -> Result<std::result::Result<(), T>> {
+        self.0.modify(|slot| match slot {
+            Some(_) => Err(value),
+            None => { *slot = Some(value); Ok(()) }
+        })
+    }
+
+    /// Whether a value is latched.
+    #[inline]
+    pub fn is_set(&self) -> Result<bool> { self.0.value().map(|slot| slot.is_some()) }
+
+    /// Wait until a value is latched, and run `f` on it.
+    ///
+    /// # Arguments
+    ///
+    /// * `timeout` - Maximum wait time
+    /// * `f` - Function reading the latched value
+    pub fn wait_with<R>(&self, timeout: Option<Duration>, f: impl FnOnce(&T) -> R) -> Result<R> {
+        self.0.wait(timeout, Option::is_some).map(|slot| f(slot.as_ref().unwrap()))
+    }
+}
+
+impl<T: Clone> LatchValue<T> {
+    /// Copy of the latched value, if any.
+    #[inline]
+    pub fn get(&self) -> Result<Option<T>> { self.0.value().map(|slot| slot.clone()) }
+
+    /// Wait until a value is latched, and return a copy of it.
+    ///
+    /// # Arguments
+    ///
+    /// * `timeout` - Maximum wait time
+    #[inline]
+    pub fn wait(&self, timeout: Option<Duration>) -> Result<T> {
+        self.wait_with(timeout, T::clone)
+    }
+}
+
+impl<T> Clone for LatchValue<T> {
+    #[inline]
+    fn clone(&self) -> Self { Self(self.0.clone()) }
+}
+
+impl<T> Default for LatchValue<T> {
+    #[inline]
+    fn default() -> Self { Self::new() }
+}
@@ -0,0 +1,89 @@
+use std::time::{ Duration, Instant };
+use crate::{ WaitEvent, Result, timer::TimerCheck };
+
+// ------------------------------ DATA TYPES ----------------------------------
+/// Lease which a holder must [`renew`](Lease::renew) within its period, otherwise it expires and observers waiting with
+/// [`wait_expired`](Lease::wait_expired) are woken.
+///
+/// # Examples
+///
+/// ```rust
+/// # use std::time::Duration;
+/// use sync_wait_object::Lease;
+///
+/// let lease = Lease::new(Duration::from_millis(30));
+/// assert!(lease.acquire().unwrap());
+/// assert!(!lease.acquire().unwrap());
+/// assert!(lease.renew().unwrap());
+///
+/// // the holder stops renewing
+/// lease.wait_expired(Some(Duration::from_secs(1))).unwrap();
+/// assert!(!lease.is_held().unwrap());
+/// assert!(lease.acquire().unwrap());
+/// ```
+#[derive(Clone)]
+pub struct Lease {
+    period: Duration,
+    expires_at: WaitEvent<Option<Instant>>
+}
+
+// ------------------------------ IMPLEMENTATIONS ------------------------------
+impl Lease {
+    /// Create an unheld lease, which must be renewed within `period` once acquired.
+    pub fn new(period: Duration) -> Self {
+        Self { period, expires_at: WaitEvent::new_init(None) }
+    }
+
+    #[inline]
+    pub fn period(&self) -> Duration { self.period }
+
+    /// Whether the lease is currently held and not expired.
+    pub fn is_held(&self) -> Result<bool> {
+        self.expires_at.value().map(|e| held(&e, Instant::now()))
+    }
+
+    /// Acquire the lease if it is not held (or has expired). Returns whether the lease was acquired.
+    pub fn acquire(&self) -> Result<bool> {
+        let period = self.period;
+        self.expires_at.modify(|e| {
+            let now = Instant::now();
+            let free = !held(e, now);
+            if free { *e = Some(now + period) }
+            free
+        })
+    }
+
+    /// Extend a held lease by another period. Returns `false` if the lease has already expired or been released.
+    pub fn renew(&self) -> Result<bool> {
+        let period = self.period;
+        self.expires_at.modify(|e| {
+            let now = Instant::now();
+            let valid = held(e, now);
+            if valid { *e = Some(now + period) }
+            valid
+        })
+    }
+
+    /// Give up the lease.
+    pub fn release(&self) -> Result<()> {
+        self.expires_at.modify(|e| *e = None)
+    }
+
+    /// Wait until the lease is not held anymore (expired or released), or timed-out from `timeout`.
+    pub fn wait_expired(&self, timeout: Option<Duration>) -> Result<()> {
+        self.expires_at.wait_timer(timeout, |e, now| match e {
+            Some(at) if *at > now => TimerCheck::Pending(Some(*at)),
+            _ => TimerCheck::Ready
+        }).map(|_| ())
+    }
+
+    /// Wait until the lease is held, or timed-out from `timeout`.
+    pub fn wait_acquired(&self, timeout: Option<Duration>) -> Result<()> {
+        self.expires_at.wait(timeout, |e| held(e, Instant::now())).map(|_| ())
+    }
+}
+
+#[inline]
+fn held(expires_at: &Option<Instant>, now: Instant) -> bool {
+    expires_at.is_some_and(|at| at > now)
+}
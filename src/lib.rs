@@ -1,11 +1,121 @@
 #![doc = include_str!("../README.md")]
 
-use std::{time, time::Duration, ops::Deref, sync::{Arc, Condvar, Mutex, MutexGuard}, mem};
+use std::{time, time::Duration, sync::{Arc, Mutex, MutexGuard, Weak}, mem};
 use std::ops::DerefMut;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
 
 #[cfg(windows)]
 pub mod windows;
 
+pub mod backend;
+
+mod builder;
+mod cancel;
+mod chain;
+mod channel;
+mod claim;
+mod coalescing;
+mod collector;
+mod completion;
+mod deadline;
+mod dependency;
+#[cfg(feature = "debug")]
+pub mod debug;
+mod drain;
+mod error_event;
+mod event;
+mod flags;
+mod freeze;
+mod group;
+mod handshake;
+mod heartbeat;
+mod hold;
+#[cfg(any(unix, windows))]
+pub mod ipc;
+#[cfg(feature = "rayon")]
+mod jobs;
+mod latch;
+mod lease;
+mod pausable;
+mod period_gate;
+#[cfg(any(target_os = "linux", windows))]
+mod pi_mutex;
+mod policy;
+#[cfg(feature = "profile")]
+mod profile;
+mod queued;
+#[cfg(any(unix, windows))]
+mod raw_trigger;
+mod readiness;
+mod ring;
+#[cfg(feature = "registry")]
+mod registry;
+#[cfg(feature = "testing")]
+pub mod testing;
+mod scope;
+mod snapshot;
+mod split;
+mod state_machine;
+#[cfg(feature = "stats")]
+mod stats;
+mod static_event;
+mod strategy;
+mod throttle;
+mod timer;
+mod watchdog;
+pub use backend::{ EventBackend, NonPoisoning, StdBackend };
+#[cfg(feature = "lock_api")]
+pub use backend::{ ParkCondvar, RawBackend, RawCondvar };
+#[cfg(all(feature = "futex", target_os = "linux"))]
+pub use backend::{ FutexBackend, FutexCondvar, FutexMutex };
+#[cfg(feature = "parking_lot")]
+pub use backend::ParkingLotBackend;
+pub use builder::WaitEventBuilder;
+pub use cancel::CancellationToken;
+pub use chain::ChainGuard;
+pub use channel::{ SignalingReceiver, SignalingSender, signaling_channel };
+pub use claim::ClaimEvent;
+pub use coalescing::CoalescingEvent;
+pub use collector::ResultCollector;
+pub use completion::Completion;
+pub use deadline::Deadline;
+pub use dependency::DependencyGraph;
+pub use drain::{ DrainGate, ActivityGuard };
+pub use error_event::{ ErrorEvent, wait_result };
+pub use event::{ Event, EventBuilder, EventMode };
+pub use flags::{ EventFlags, WaitMode };
+pub use freeze::FreezeGuard;
+pub use group::EventGroup;
+pub use handshake::Handshake;
+pub use heartbeat::Heartbeat;
+pub use hold::HoldGuard;
+#[cfg(feature = "rayon")]
+pub use jobs::{ WaitableJob, spawn_waitable, spawn_waitable_in };
+pub use latch::LatchValue;
+pub use lease::Lease;
+pub use pausable::Pausable;
+pub use period_gate::PeriodGate;
+#[cfg(any(target_os = "linux", windows))]
+pub use pi_mutex::{ PiMutex, PiMutexGuard };
+pub use policy::WaitPolicy;
+#[cfg(feature = "profile")]
+pub use profile::{ SetRecord, ThreadLabel, WakeEdge, WakeReport, PROFILE_CAPACITY };
+pub use queued::QueuedEvent;
+#[cfg(any(unix, windows))]
+pub use raw_trigger::RawTrigger;
+pub use readiness::Readiness;
+pub use ring::{ RingConsumer, RingProducer, ring_buffer };
+pub use scope::{ scope, Scope, ScopedTask };
+pub use snapshot::{ Snapshot, snapshot_all };
+pub use split::{ EventReader, EventSetter };
+pub use state_machine::StateMachine;
+#[cfg(feature = "stats")]
+pub use stats::WaitStats;
+pub use static_event::StaticEvent;
+pub use strategy::{ Backoff, SpinBackoff, WaitStrategy, YieldBackoff };
+pub use throttle::{ ThrottledSetter, ThrottleMode };
+pub use watchdog::{ Watchdog, WatchdogFeeder };
+
 // ------------------------------ DATA TYPES ----------------------------------
 #[derive(Debug, PartialEq)]
 pub enum WaitObjectError {
@@ -16,11 +126,29 @@ pub enum WaitObjectError {
     SynchronizationBroken,
 
     /// Wait is timed out
-    Timeout
+    Timeout,
+
+    /// State change is not allowed from the current state
+    InvalidTransition,
+
+    /// Linking events would create a cycle
+    CycleDetected,
+
+    /// Named object exists with another type
+    TypeMismatch,
+
+    /// Wait is interrupted by a [`CancellationToken`]
+    Cancelled,
+
+    /// All setters of the event are dropped, see [`WaitEvent::split`]
+    Disconnected
 }
 
 pub type Result<T> = std::result::Result<T, WaitObjectError>;
 
+/// Interval at which multi-event waits (e.g. [`WaitEvent::wait_any_of`], [`WaitEvent::wait_quorum_of`]) re-check every event.
+pub const ANY_POLL_INTERVAL: Duration = Duration::from_millis(1);
+
 /// Create a wait event object of any type T. To use this wait object in multi-threaded scenario, just clone the object and distribute it.
 ///
 /// This wait object is just a wrapper of Mutex and Condvar combination with the suggested pattern (from Rust document) for waiting a value.
@@ -66,46 +194,498 @@ pub type Result<T> = std::result::Result<T, WaitObjectError>;
 /// assert_eq!(current, 1);
 /// ```
 ///
-#[derive(Clone)]
-pub struct WaitEvent<T>(Arc<(Mutex<T>, Condvar)>);
+/// The blocking machinery is provided by the backend `B`, see [`backend`].
+pub struct WaitEvent<T, B = StdBackend<T>>(Arc<Inner<T, B>>);
+
+struct Inner<T, B> {
+    backend: B,
+    listeners: Arc<Mutex<Listeners<T>>>,
+    default_timeout: Option<Duration>,
+    name: Option<Arc<str>>,
+    /// Number of threads blocked on the backend, only changed under the state lock
+    waiters: AtomicUsize,
+    /// Number of setters among `waiters`, blocked by a hold or a freeze
+    blocked_setters: AtomicUsize,
+    /// Whether a waiter of an [`AutoResetEvent`] keeps the signal claimed, see [`AutoResetEvent::wait_guarded`]
+    claimed: AtomicBool,
+    /// Waiters of an [`AutoResetEvent`] admitted by [`AutoResetEvent::release`] and not woken yet
+    permits: AtomicUsize,
+    /// Number of live [`EventSetter`]s
+    setters: AtomicUsize,
+    /// Set under the state lock once the last [`EventSetter`] drops
+    disconnected: AtomicBool,
+    /// Number of blocked waiters not woken by a notification yet, only changed under the state lock
+    #[cfg(feature = "debug")]
+    unnotified: AtomicUsize,
+    /// Incremented by each notification of blocked waiters, under the state lock
+    #[cfg(feature = "debug")]
+    notify_epoch: std::sync::atomic::AtomicU64,
+    /// Setters wait until this instant, see [`WaitEvent::wait_and_hold`]
+    held_until: Mutex<Option<time::Instant>>,
+    /// Number of live [`FreezeGuard`]s, only changed under the state lock
+    frozen: AtomicUsize,
+    #[cfg(feature = "stats")]
+    stats: Mutex<stats::WaitStats>,
+    #[cfg(feature = "profile")]
+    profile: Mutex<profile::Profiler>,
+    #[cfg(feature = "testing")]
+    chaos: Mutex<Option<testing::Chaos>>
+}
+
+/// Change listener, which returns `false` once it should be unregistered.
+type Listener<T> = Arc<dyn Fn(&T) -> bool + Send + Sync>;
+
+struct Listeners<T> {
+    next_id: u64,
+    entries: Vec<(u64, Listener<T>)>
+}
+
+/// Subscription from [`WaitEvent::on_change`]. The callback is unregistered when this guard drops.
+#[must_use = "the callback is unregistered when the guard drops"]
+pub struct SubscriptionGuard<T> {
+    listeners: Weak<Mutex<Listeners<T>>>,
+    id: u64
+}
+
+/// Result of a successful wait, along with how long the caller was actually blocked.
+#[derive(Debug)]
+pub struct Timed<V> {
+    pub value: V,
+    pub waited: Duration
+}
+
+/// Result of [`WaitEvent::wait_traced`], along with the duration of each checker evaluation.
+#[derive(Debug)]
+pub struct Traced<V> {
+    pub value: V,
+    pub checks: Vec<Duration>
+}
+
+/// Which waiters a state change wakes up, see [`WaitEvent::set_state_notify`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum NotifyPolicy {
+    /// Wake all waiters, which re-check their condition
+    #[default]
+    All,
+
+    /// Wake a single waiter. Only suits waiters which all wait for the same condition, e.g. single-consumer hand-offs:
+    /// a waiter woken up for a condition it does not wait for does not pass the wake-up on.
+    One
+}
+
+/// Context given to the checker of [`WaitEvent::wait_ctx`], so it can adapt to the time left.
+#[derive(Debug, Clone, Copy)]
+pub struct WaitCtx {
+    deadline: Deadline,
+    wakeups: usize
+}
+
+/// Outcome of [`WaitEvent::wait_or_timeout`]. Both variants hold the lock guard.
+pub enum WaitOutcome<'a, T: 'a, B: EventBackend<T> + 'a = StdBackend<T>> {
+    /// The checker passed
+    Passed(B::Guard<'a>),
+
+    /// Wait is timed out, the guard holds the last observed state
+    TimedOut(B::Guard<'a>)
+}
+
+/// Result of the `wait_status` family, where a timeout is an expected outcome rather than an error. `G` is the lock
+/// guard for [`WaitEvent::wait_status`], or `()` for the boolean events.
+#[derive(Debug)]
+pub enum WaitStatus<G> {
+    /// The wait condition is met
+    Signaled(G),
+
+    /// Wait is timed out
+    TimedOut
+}
+
+/// Result of [`WaitEvent::wait_timeout_result`].
+#[derive(Debug)]
+pub enum TimeoutResult<G> {
+    /// The wait condition is met
+    Signaled(G),
+
+    /// Wait is timed out. `progressed` tells whether the state was seen changing during the wait.
+    TimedOut { progressed: bool }
+}
 
 /// Wrapper of [`WaitEvent`] of type `bool`, which focuses on waiting for `true` without resetting.
 #[derive(Clone)]
 pub struct ManualResetEvent(WaitEvent<bool>);
 
 /// Wrapper of [`WaitEvent`] of type `bool`, which focuses on waiting for `true` with automatic reset to `false`.
+///
+/// The reset happens when the wait ends ([`wait`](SignalWaitable::wait)), after a closure of the waiter completes
+/// ([`wait_and_then`](Self::wait_and_then)), or when a guard drops ([`wait_deferred`](Self::wait_deferred),
+/// [`wait_guarded`](Self::wait_guarded)). Resetting to a caller-supplied value needs a state richer than `bool`, see
+/// [`WaitEvent::wait_reset`] and [`WaitEvent::wait_reset_with`].
 #[derive(Clone)]
 pub struct AutoResetEvent(WaitEvent<bool>);
 
+/// RAII guard from [`ManualResetEvent::set_scoped`], which resets the event when dropped.
+#[must_use = "the event is reset as soon as the guard drops"]
+pub struct SetGuard(ManualResetEvent);
+
+/// RAII guard from [`ManualResetEvent::completion_guard`], which sets the event when dropped.
+#[must_use = "the event is set as soon as the guard drops"]
+pub struct CompletionGuard(ManualResetEvent);
+
+/// RAII guard from [`AutoResetEvent::wait_guarded`], which releases the claim on the event when dropped.
+#[must_use = "the claim is released as soon as the guard drops"]
+pub struct ProcessingGuard(AutoResetEvent);
+
+/// RAII guard from [`AutoResetEvent::wait_deferred`], which resets the event when dropped.
+pub struct ResetGuard<'a>(Option<MutexGuard<'a, bool>>, &'a AutoResetEvent);
+
 // Boolean signal with ability to wait and set state.
 pub trait SignalWaitable {
     fn wait_until_set(&self) -> Result<()>;
+
+    /// Wait until the signal is set, or timed-out from `timeout`. `Duration::ZERO` only tries once, and
+    /// `Duration::MAX` waits forever, on every implementation (see [`Deadline::from_timeout`]).
     fn wait(&self, timeout: Duration) -> Result<()>;
     fn set(&mut self) -> Result<()>;
     fn reset(&mut self) -> Result<()>;
+
+    /// Whether the signal is currently set, without waiting nor consuming it.
+    fn is_set(&self) -> Result<bool>;
 }
 
 // ------------------------------ IMPLEMENTATIONS ------------------------------
 impl<T> WaitEvent<T> {
     #[inline]
     pub fn new_init(initial_state: T) -> Self {
-        Self(Arc::new((Mutex::new(initial_state), Condvar::new())))
+        Self::with_backend(initial_state)
+    }
+}
+
+impl<T, B: EventBackend<T>> WaitEvent<T, B> {
+    /// Create a wait event with backend `B`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use std::time::Duration;
+    /// use sync_wait_object::{WaitEvent, StdBackend};
+    ///
+    /// let mut ev = WaitEvent::<_, StdBackend<_>>::with_backend(0);
+    /// ev.set_state(1).unwrap();
+    /// assert_eq!(*ev.wait(Some(Duration::ZERO), |v| *v == 1).unwrap(), 1);
+    /// ```
+    #[inline]
+    pub fn with_backend(initial_state: T) -> Self {
+        Self::with_config(initial_state, None, None)
+    }
+
+    pub(crate) fn with_config(initial_state: T, default_timeout: Option<Duration>, name: Option<Arc<str>>) -> Self {
+        Self(Arc::new(Inner {
+            backend: B::new(initial_state),
+            listeners: Arc::new(Mutex::new(Listeners { next_id: 0, entries: Vec::new() })),
+            default_timeout,
+            name,
+            waiters: AtomicUsize::new(0),
+            blocked_setters: AtomicUsize::new(0),
+            claimed: AtomicBool::new(false),
+            permits: AtomicUsize::new(0),
+            setters: AtomicUsize::new(0),
+            disconnected: AtomicBool::new(false),
+            #[cfg(feature = "debug")]
+            unnotified: AtomicUsize::new(0),
+            #[cfg(feature = "debug")]
+            notify_epoch: std::sync::atomic::AtomicU64::new(0),
+            held_until: Mutex::new(None),
+            frozen: AtomicUsize::new(0),
+            #[cfg(feature = "stats")]
+            stats: Mutex::new(stats::WaitStats::new()),
+            #[cfg(feature = "profile")]
+            profile: Mutex::new(profile::Profiler::new()),
+            #[cfg(feature = "testing")]
+            chaos: Mutex::new(None)
+        }))
+    }
+
+    pub fn value(&self) -> Result<B::Guard<'_>> {
+        self.lock_state()
+    }
+
+    /// Evaluate `checker` on the current state without waiting: only the state lock is taken, and released before
+    /// returning. For pollers which test the condition in a loop of their own.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use sync_wait_object::WaitEvent;
+    ///
+    /// let mut progress = WaitEvent::new_init(40);
+    /// assert!(!progress.check(|p| *p == 100).unwrap());
+    ///
+    /// progress.set_state(100).unwrap();
+    /// assert!(progress.check(|p| *p == 100).unwrap());
+    /// ```
+    #[inline]
+    pub fn check(&self, checker: impl FnOnce(&T) -> bool) -> Result<bool> {
+        self.lock_state().map(|state| checker(&*state))
+    }
+
+    /// Extract the state if this is the last handle of the event, or give the handle back otherwise.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use sync_wait_object::WaitEvent;
+    ///
+    /// let ev = WaitEvent::new_init(vec![1, 2, 3]);
+    /// let worker = ev.clone();
+    ///
+    /// let ev = ev.try_into_inner().unwrap_err();
+    /// drop(worker);
+    /// assert_eq!(ev.try_into_inner().ok(), Some(vec![1, 2, 3]));
+    /// ```
+    pub fn try_into_inner(self) -> std::result::Result<T, Self> {
+        if !self.is_unique() { return Err(self) }
+        let inner = self.0.clone();
+        drop(self);
+        Arc::try_unwrap(inner).map(|inner| inner.backend.into_inner()).map_err(Self)
+    }
+
+    /// Number of handles (clones) of this event, including this one.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use sync_wait_object::WaitEvent;
+    ///
+    /// let ev = WaitEvent::new_init(0);
+    /// let handle = ev.clone();
+    /// assert_eq!(ev.handle_count(), 2);
+    ///
+    /// drop(handle);
+    /// assert!(ev.is_unique());
+    /// ```
+    #[inline]
+    pub fn handle_count(&self) -> usize { Arc::strong_count(&self.0) }
+
+    /// Whether this is the only handle of the event.
+    #[inline]
+    pub fn is_unique(&self) -> bool { self.handle_count() == 1 }
+
+    /// Default timeout of this event, see [`WaitEventBuilder::default_timeout`].
+    #[inline]
+    pub fn default_timeout(&self) -> Option<Duration> { self.0.default_timeout }
+
+    /// Name of this event, see [`WaitEventBuilder::name`].
+    #[inline]
+    pub fn name(&self) -> Option<&str> { self.0.name.as_deref() }
+
+    /// Number of threads currently blocked waiting on this event. Busy-waits (e.g. [`wait_spin`](Self::wait_spin)) and
+    /// setters blocked by a hold or a freeze are not counted.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use std::{thread, time::Duration};
+    /// use sync_wait_object::WaitEvent;
+    ///
+    /// let ev = WaitEvent::new_init(0);
+    /// let waiter = ev.clone();
+    /// assert_eq!(ev.waiter_count(), 0);
+    ///
+    /// thread::spawn(move || drop(waiter.wait(None, |v| *v == 1)));
+    /// while ev.waiter_count() == 0 { thread::sleep(Duration::from_millis(1)) }
+    /// assert_eq!(ev.waiter_count(), 1);
+    ///
+    /// let hold = ev.wait_and_hold(None, Duration::from_secs(5), |_| true).unwrap();
+    /// let mut setter = ev.clone();
+    /// let set = thread::spawn(move || setter.set_state(1).unwrap());
+    /// thread::sleep(Duration::from_millis(20));
+    /// assert_eq!(ev.waiter_count(), 1);
+    ///
+    /// drop(hold);
+    /// set.join().unwrap();
+    /// ```
+    #[inline]
+    pub fn waiter_count(&self) -> usize {
+        self.0.waiters.load(Ordering::SeqCst).saturating_sub(self.0.blocked_setters.load(Ordering::SeqCst))
+    }
+
+    /// Whether any thread is currently blocked waiting on this event. Lock-free, so producers can cheaply skip work
+    /// nobody is waiting for.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use sync_wait_object::WaitEvent;
+    ///
+    /// let mut progress = WaitEvent::new_init(String::new());
+    /// if progress.has_waiters() {
+    ///     progress.set_state(format!("{} of {} done", 1, 10)).unwrap();
+    /// }
+    /// assert_eq!(*progress.value().unwrap(), "");
+    /// ```
+    #[inline]
+    pub fn has_waiters(&self) -> bool { self.waiter_count() > 0 }
+
+    /// Wait until the `checker` returns true, or timed-out from `timeout`, then run `then` on the state under the lock
+    /// and notify waiters. The lock is released before returning, so it cannot be held across unrelated work by
+    /// mistake. Prefer this over [`wait`](Self::wait), which returns the lock guard itself.
+    ///
+    /// # Arguments
+    ///
+    /// * `timeout` - Maximum wait time, `None` for the default timeout (see [`WaitPolicy`])
+    /// * `checker` - Checker function, once it returns `true`, the wait ends
+    /// * `then` - Function run on the state once the wait ends
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use std::{thread, time::Duration};
+    /// use sync_wait_object::WaitEvent;
+    ///
+    /// let jobs = WaitEvent::new_init(Vec::new());
+    /// let mut producer = jobs.clone();
+    /// thread::spawn(move || producer.set_state(vec!["build", "test"]).unwrap());
+    ///
+    /// let next = jobs.wait_then(Some(Duration::from_secs(1)), |j| !j.is_empty(), |j| j.remove(0)).unwrap();
+    /// assert_eq!(next, "build");
+    /// ```
+    pub fn wait_then<R>(&self, timeout: Option<Duration>, checker: impl FnMut(&T) -> bool, then: impl FnOnce(&mut T) -> R) -> Result<R> {
+        let mut state = self.wait_for_update(timeout.or_else(|| self.unset_timeout()), checker)?;
+        let r = then(&mut *state);
+        self.commit(state);
+        Ok(r)
     }
 
-    pub fn value(&self) -> Result<MutexGuard<T>> {
-        self.0.0.lock().map_err(|e| e.into())
+    /// Wait until the `checker` returns true, or timed-out from `timeout`, then run `f` on the state under the lock and
+    /// return its owned result. Read-only counterpart of [`wait_then`](Self::wait_then), which does not notify.
+    ///
+    /// # Arguments
+    ///
+    /// * `timeout` - Maximum wait time, `None` for the default timeout (see [`WaitPolicy`])
+    /// * `checker` - Checker function, once it returns `true`, the wait ends
+    /// * `f` - Function mapping the state to the result
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use std::time::Duration;
+    /// use sync_wait_object::WaitEvent;
+    ///
+    /// let status = WaitEvent::new_init(String::from("ready: 3 nodes"));
+    /// let len = status.wait_map(Some(Duration::from_secs(1)), |s| s.starts_with("ready"), |s| s.len()).unwrap();
+    /// assert_eq!(len, 14);
+    /// ```
+    pub fn wait_map<R>(&self, timeout: Option<Duration>, checker: impl FnMut(&T) -> bool, f: impl FnOnce(&T) -> R) -> Result<R> {
+        self.wait(timeout, checker).map(|state| f(&*state))
     }
 
-    /// Wait until the `checker` returns true, or timed-out from `timeout`.
+    /// Wait until the `checker` returns true, or timed-out from `timeout`. The returned guard holds the state lock, so
+    /// keep it short-lived; [`wait_then`](Self::wait_then) covers most uses without exposing the guard.
     ///
     /// # Arguments
     ///
-    /// * `timeout` - Maximum wait time
+    /// * `timeout` - Maximum wait time, `None` for the default timeout (see [`WaitPolicy`])
     /// * `checker` - Checker function, once it returns `true`, the wait ends
-    pub fn wait(&self, timeout: Option<Duration>, checker: impl FnMut(&T) -> bool) -> Result<MutexGuard<T>> {
+    pub fn wait(&self, timeout: Option<Duration>, checker: impl FnMut(&T) -> bool) -> Result<B::Guard<'_>> {
         match timeout {
             Some(_) => self.wait_with_waiter(timeout, checker),
-            None => self.wait_with_waiter(timeout, checker)
+            None => self.wait_with_waiter(self.unset_timeout(), checker)
+        }
+    }
+
+    /// Wait until the `checker` returns true, without timeout even if the event has a default timeout.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use std::{thread, time::Duration};
+    /// use sync_wait_object::WaitEvent;
+    ///
+    /// let ev = WaitEvent::builder().default_timeout(Duration::from_millis(1)).build(0);
+    /// let mut setter = ev.clone();
+    ///
+    /// thread::spawn(move || {
+    ///     thread::sleep(Duration::from_millis(20));
+    ///     setter.set_state(1).unwrap();
+    /// });
+    /// assert_eq!(*ev.wait_forever(|v| *v == 1).unwrap(), 1);
+    /// ```
+    #[inline]
+    pub fn wait_forever(&self, checker: impl FnMut(&T) -> bool) -> Result<B::Guard<'_>> {
+        self.wait_with_waiter(None, checker)
+    }
+
+    /// Same as [`wait`](Self::wait), but the wait times out at `deadline` rather than after a duration, so several
+    /// waits can share one deadline. A passed deadline checks the state once; `None` waits forever.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use std::{thread, time::{Duration, Instant}};
+    /// use sync_wait_object::{WaitEvent, WaitObjectError};
+    ///
+    /// let (config, network) = (WaitEvent::new_init(true), WaitEvent::new_init(false));
+    /// let deadline = Some(Instant::now() + Duration::from_millis(20));
+    ///
+    /// assert!(config.wait_deadline(deadline, |ready| *ready).is_ok());
+    /// assert!(matches!(network.wait_deadline(deadline, |ready| *ready), Err(WaitObjectError::Timeout)));
+    ///
+    /// // a set which does not pass the checker does not push the deadline back
+    /// let mut flapping = network.clone();
+    /// thread::spawn(move || {
+    ///     thread::sleep(Duration::from_millis(80));
+    ///     flapping.set_state(false).unwrap();
+    /// });
+    /// let start = Instant::now();
+    /// assert!(network.wait_deadline(Some(start + Duration::from_millis(100)), |ready| *ready).is_err());
+    /// assert!(start.elapsed() < Duration::from_millis(160));
+    /// ```
+    #[inline]
+    pub fn wait_deadline(&self, deadline: Option<time::Instant>, checker: impl FnMut(&T) -> bool) -> Result<B::Guard<'_>> {
+        self.wait_with_waiter(deadline.map(|d| d.saturating_duration_since(time::Instant::now())), checker)
+    }
+
+    /// Same as [`wait_reset`](Self::wait_reset), but the wait times out at `deadline` (see
+    /// [`wait_deadline`](Self::wait_deadline)).
+    #[inline]
+    pub fn wait_reset_deadline(&self, deadline: Option<time::Instant>, reset: impl FnMut() -> T, checker: impl FnMut(&T) -> bool) -> Result<T> {
+        self.wait_and_reset_with_waiter(deadline.map(|d| d.saturating_duration_since(time::Instant::now())), checker, reset)
+    }
+
+    /// Busy-wait until the `checker` returns true, without ever parking the thread. The state is re-checked up to
+    /// `max_spins` times, then the wait fails with [`WaitObjectError::Timeout`].
+    ///
+    /// # Arguments
+    ///
+    /// * `max_spins` - Maximum number of re-checks
+    /// * `checker` - Checker function, once it returns `true`, the wait ends
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use sync_wait_object::{WaitEvent, WaitObjectError};
+    ///
+    /// let ev = WaitEvent::new_init(1);
+    /// assert_eq!(*ev.wait_spin(100, |v| *v == 1).unwrap(), 1);
+    /// assert_eq!(ev.wait_spin(100, |v| *v == 2).err(), Some(WaitObjectError::Timeout));
+    /// ```
+    pub fn wait_spin(&self, max_spins: u32, mut checker: impl FnMut(&T) -> bool) -> Result<B::Guard<'_>> {
+        let mut state = self.lock_state()?;
+        for _ in 0..max_spins {
+            if checker(&*state) { return Ok(state) }
+            drop(state);
+            std::hint::spin_loop();
+            state = self.lock_state()?;
+        }
+        if checker(&*state) { Ok(state) } else { Err(WaitObjectError::Timeout) }
+    }
+
+    /// Same as [`wait_spin`](Self::wait_spin), but falls back to [`wait`](Self::wait) with `timeout` once the spin
+    /// budget is exceeded.
+    pub fn wait_spin_or_park(&self, timeout: Option<Duration>, max_spins: u32, mut checker: impl FnMut(&T) -> bool) -> Result<B::Guard<'_>> {
+        match self.wait_spin(max_spins, &mut checker) {
+            Err(WaitObjectError::Timeout) => self.wait(timeout, checker),
+            r => r
         }
     }
 
@@ -113,7 +693,7 @@ impl<T> WaitEvent<T> {
     ///
     /// # Arguments
     ///
-    /// * `timeout` - Maximum wait time
+    /// * `timeout` - Maximum wait time, `None` for the default timeout (see [`WaitPolicy`])
     /// * `reset` - Function that provides a reset value
     /// * `checker` - Checker function, once it returns `true`, the wait ends
     ///
@@ -142,82 +722,983 @@ impl<T> WaitEvent<T> {
     pub fn wait_reset(&self, timeout: Option<Duration>, reset: impl FnMut() -> T, checker: impl FnMut(&T) -> bool) -> Result<T> {
         match timeout {
             Some(_) => self.wait_and_reset_with_waiter(timeout, checker, reset),
-            None => self.wait_and_reset_with_waiter(timeout, checker, reset)
-        }
-    }
-
-    pub fn wait_with_waiter(&self, timeout: Option<Duration>, mut checker: impl FnMut(&T) -> bool) -> Result<MutexGuard<T>> {
-        let (lock, cond) = self.0.deref();
-        let mut state = lock.lock()?;
-        let waiter = Self::create_waiter(timeout);
-        let mut continue_wait = waiter();
-        let mut pass = checker(&*state);
-        while continue_wait && !pass {
-            state = match timeout {
-                Some(t) => {
-                    let (g, _) = cond.wait_timeout(state, t)?;
-                    g
-                },
-                None => cond.wait(state)?
-            };
-            continue_wait = waiter();
-            pass = checker(&*state);
+            None => self.wait_and_reset_with_waiter(self.unset_timeout(), checker, reset)
         }
-        if pass { Ok(state) }
-        else { Err(WaitObjectError::Timeout) }
     }
 
-    pub fn wait_and_reset_with_waiter(&self, timeout: Option<Duration>, checker: impl FnMut(&T) -> bool, mut reset: impl FnMut() -> T) -> Result<T> {
-        let state = self.wait_with_waiter(timeout, checker);
-        state.map(|mut g| mem::replace(g.deref_mut(), reset()))
+    /// Wait until the `checker` returns true, or timed-out from `timeout`. If the wait ends from `checker` condition, the
+    /// state is replaced by the value `reset` computes from the final state, and the final state is returned.
+    ///
+    /// # Arguments
+    ///
+    /// * `timeout` - Maximum wait time, `None` for the default timeout (see [`WaitPolicy`])
+    /// * `checker` - Checker function, once it returns `true`, the wait ends
+    /// * `reset` - Function that provides a reset value from the final state
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use std::time::Duration;
+    /// use sync_wait_object::WaitEvent;
+    ///
+    /// let wait = WaitEvent::new_init(3);
+    /// let observed = wait.wait_reset_with(Some(Duration::from_secs(1)), |v| *v > 0, |v| v - 1).unwrap();
+    /// assert_eq!(observed, 3);
+    /// assert_eq!(*wait.value().unwrap(), 2);
+    /// ```
+    pub fn wait_reset_with(&self, timeout: Option<Duration>, checker: impl FnMut(&T) -> bool, reset: impl FnOnce(&T) -> T) -> Result<T> {
+        let mut state = self.wait_for_update(timeout.or_else(|| self.unset_timeout()), checker)?;
+        let next = reset(&*state);
+        Ok(mem::replace(state.deref_mut(), next))
     }
 
-    /// Synchronously change state of WaitObject by value
-    pub fn set_state(&mut self, new_state: T) -> Result<()> {
-        let (lock, cond) = self.0.deref();
-        let mut state = lock.lock()?;
-        *state = new_state;
-        cond.notify_all();
-        Ok(())
+    /// Same as [`wait`](Self::wait), but also reports how long the wait actually took.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use std::time::Duration;
+    /// use sync_wait_object::WaitEvent;
+    ///
+    /// let wait = WaitEvent::new_init(1);
+    /// let result = wait.wait_timed(Some(Duration::from_secs(1)), |v| *v == 1).unwrap();
+    /// assert_eq!(*result.value, 1);
+    /// assert!(result.waited < Duration::from_secs(1));
+    /// ```
+    pub fn wait_timed(&self, timeout: Option<Duration>, checker: impl FnMut(&T) -> bool) -> Result<Timed<B::Guard<'_>>> {
+        let start = time::Instant::now();
+        self.wait(timeout, checker).map(|value| Timed { value, waited: start.elapsed() })
     }
 
-    /// Synchronously change state of WaitObject by a function's return value
+    /// Same as [`wait_status`](Self::wait_status), but also records the duration of each `checker` evaluation, to
+    /// diagnose expensive checkers.
     ///
-    /// # Example
+    /// # Examples
     ///
     /// ```rust
-    /// # use std::{time::Duration, thread};
+    /// # use std::time::Duration;
     /// use sync_wait_object::WaitEvent;
     ///
     /// let wait = WaitEvent::new_init(0);
-    /// let mut w1 = wait.clone();
-    /// let mut w2 = wait.clone();
-    /// let mut w3 = wait.clone();
+    /// let traced = wait.wait_traced(Some(Duration::from_millis(10)), |v| *v == 1).unwrap();
+    /// assert!(traced.value.is_timed_out());
+    /// assert!(traced.evaluations() >= 1);
+    /// println!("checker took {:?} in total", traced.total());
+    /// ```
+    pub fn wait_traced(&self, timeout: Option<Duration>, mut checker: impl FnMut(&T) -> bool) -> Result<Traced<WaitStatus<B::Guard<'_>>>> {
+        let mut checks = Vec::new();
+        let status = self.wait_status(timeout, |v| {
+            let start = time::Instant::now();
+            let pass = checker(v);
+            checks.push(start.elapsed());
+            pass
+        })?;
+        Ok(Traced { value: status, checks })
+    }
+
+    /// Same as [`wait`](Self::wait), but the `checker` also gets a [`WaitCtx`] with the time left and the number of
+    /// wake-ups so far, e.g. to accept a degraded condition close to the deadline.
     ///
-    /// thread::spawn(move || w1.set_state_func(|v| v + 1));
-    /// thread::spawn(move || w2.set_state_func(|v| v + 1));
-    /// thread::spawn(move || w3.set_state_func(|v| v + 1));
+    /// # Examples
     ///
-    /// let result = *wait.wait(Some(Duration::from_millis(200)), |v| *v == 3).unwrap();
-    /// assert_eq!(result, 3);
+    /// ```rust
+    /// # use std::{thread, time::Duration};
+    /// use sync_wait_object::WaitEvent;
+    ///
+    /// let replicas = WaitEvent::new_init(0);
+    /// let mut acks = replicas.clone();
+    /// thread::spawn(move || acks.set_state(2).unwrap());
+    ///
+    /// // all 3 replicas are preferred, but 2 will do once less than 50ms are left
+    /// let acked = *replicas.wait_ctx(Some(Duration::from_millis(100)), |n, ctx| {
+    ///     *n == 3 || (*n >= 2 && ctx.remaining().unwrap() < Duration::from_millis(50))
+    /// }).unwrap();
+    /// assert_eq!(acked, 2);
     /// ```
-    pub fn set_state_func<F>(&mut self, setter: F) -> Result<()>
-    where F: FnOnce(&T) -> T
-    {
-        let (lock, cond) = self.0.deref();
-        let mut state = lock.lock()?;
-        *state = setter(&*state);
-        cond.notify_all();
-        Ok(())
+    pub fn wait_ctx(&self, timeout: Option<Duration>, mut checker: impl FnMut(&T, &WaitCtx) -> bool) -> Result<B::Guard<'_>> {
+        let timeout = Deadline::limit(timeout.or_else(|| self.unset_timeout()))?;
+        let mut ctx = WaitCtx { deadline: Deadline::from_timeout(timeout), wakeups: 0 };
+        self.wait_with_waiter(timeout, |v| {
+            let pass = checker(v, &ctx);
+            ctx.wakeups += 1;
+            pass
+        })
     }
 
-    fn create_waiter(timeout: Option<Duration>) -> impl Fn() -> bool {
+    /// Same as [`wait_reset`](Self::wait_reset), but also reports how long the wait actually took.
+    pub fn wait_reset_timed(&self, timeout: Option<Duration>, reset: impl FnMut() -> T, checker: impl FnMut(&T) -> bool) -> Result<Timed<T>> {
         let start = time::Instant::now();
-        move || {
-            match timeout {
-                Some(t) => (time::Instant::now() - start) < t,
-                None => true
+        self.wait_reset(timeout, reset, checker).map(|value| Timed { value, waited: start.elapsed() })
+    }
+
+    pub fn wait_with_waiter(&self, timeout: Option<Duration>, checker: impl FnMut(&T) -> bool) -> Result<B::Guard<'_>> {
+        self.wait_sliced(timeout, None, checker)
+    }
+
+    /// Wait until the `checker` returns true, or timed-out from `timeout`. Unlike [`wait`](Self::wait), the `checker`
+    /// is also re-evaluated every `recheck_interval` even if the state is never set, for conditions that depend on
+    /// something which cannot notify this event.
+    ///
+    /// # Arguments
+    ///
+    /// * `timeout` - Maximum wait time, `None` for the default timeout (see [`WaitPolicy`])
+    /// * `recheck_interval` - Maximum time between two `checker` evaluations
+    /// * `checker` - Checker function, once it returns `true`, the wait ends
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use std::{thread, time::Duration, sync::{Arc, atomic::{AtomicBool, Ordering}}};
+    /// use sync_wait_object::WaitEvent;
+    ///
+    /// let wait = WaitEvent::new_init(());
+    /// let external = Arc::new(AtomicBool::new(false));
+    /// let flag = external.clone();
+    ///
+    /// thread::spawn(move || flag.store(true, Ordering::SeqCst));
+    ///
+    /// let result = wait.wait_polling(Some(Duration::from_secs(1)), Duration::from_millis(10), |_| external.load(Ordering::SeqCst));
+    /// assert!(result.is_ok());
+    /// ```
+    pub fn wait_polling(&self, timeout: Option<Duration>, recheck_interval: Duration, checker: impl FnMut(&T) -> bool) -> Result<B::Guard<'_>> {
+        self.wait_sliced(timeout.or_else(|| self.unset_timeout()), Some(recheck_interval), checker)
+    }
+
+    /// Wait until the `checker` returns true, or timed-out from `timeout`. Unlike [`wait`](Self::wait), a timeout still
+    /// yields the lock guard, so the state can be inspected or repaired without locking again.
+    ///
+    /// # Arguments
+    ///
+    /// * `timeout` - Maximum wait time, `None` for the default timeout (see [`WaitPolicy`])
+    /// * `checker` - Checker function, once it returns `true`, the wait ends
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use std::time::Duration;
+    /// use sync_wait_object::{WaitEvent, WaitOutcome};
+    ///
+    /// let wait = WaitEvent::new_init(1);
+    /// match wait.wait_or_timeout(Some(Duration::from_millis(10)), |v| *v == 2).unwrap() {
+    ///     WaitOutcome::Passed(_) => unreachable!(),
+    ///     WaitOutcome::TimedOut(mut state) => *state = 0
+    /// }
+    /// assert_eq!(*wait.value().unwrap(), 0);
+    ///
+    /// let quick = WaitEvent::builder().default_timeout(Duration::from_millis(10)).build(1);
+    /// assert!(matches!(quick.wait_or_timeout(None, |v| *v == 2).unwrap(), WaitOutcome::TimedOut(_)));
+    /// ```
+    pub fn wait_or_timeout(&self, timeout: Option<Duration>, checker: impl FnMut(&T) -> bool) -> Result<WaitOutcome<'_, T, B>> {
+        let (state, pass) = self.wait_core(timeout.or_else(|| self.unset_timeout()), None, checker)?;
+        Ok(if pass { WaitOutcome::Passed(state) } else { WaitOutcome::TimedOut(state) })
+    }
+
+    /// Same as [`wait`](Self::wait), but a timeout is reported as [`WaitStatus::TimedOut`] instead of an error.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use std::time::Duration;
+    /// use sync_wait_object::{WaitEvent, WaitStatus};
+    ///
+    /// let ev = WaitEvent::new_init(0);
+    /// match ev.wait_status(Some(Duration::from_millis(10)), |v| *v == 1).unwrap() {
+    ///     WaitStatus::Signaled(v) => println!("got {}", *v),
+    ///     WaitStatus::TimedOut => println!("nothing yet")
+    /// };
+    /// ```
+    pub fn wait_status(&self, timeout: Option<Duration>, checker: impl FnMut(&T) -> bool) -> Result<WaitStatus<B::Guard<'_>>> {
+        WaitStatus::from_result(self.wait(timeout, checker))
+    }
+
+    /// Wait until the `checker` returns true, or timed-out from `timeout`, calling `feeder` every `slice` of waiting
+    /// (e.g. to feed an external watchdog). The feeder runs without the state lock held.
+    ///
+    /// # Arguments
+    ///
+    /// * `timeout` - Maximum wait time, `None` for the default timeout (see [`WaitPolicy`])
+    /// * `slice` - Interval between two `feeder` calls
+    /// * `feeder` - Keep-alive function
+    /// * `checker` - Checker function, once it returns `true`, the wait ends
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use std::time::Duration;
+    /// use sync_wait_object::WaitEvent;
+    ///
+    /// let ev = WaitEvent::new_init(0);
+    /// let mut fed = 0;
+    /// let r = ev.wait_feeding(Some(Duration::from_millis(50)), Duration::from_millis(10), || fed += 1, |v| *v == 1);
+    /// assert!(r.is_err());
+    /// assert!(fed >= 3);
+    /// ```
+    pub fn wait_feeding(&self, timeout: Option<Duration>, slice: Duration, mut feeder: impl FnMut(), mut checker: impl FnMut(&T) -> bool) -> Result<B::Guard<'_>> {
+        let deadline = Deadline::from_timeout(Deadline::limit(timeout.or_else(|| self.unset_timeout()))?).instant();
+        loop {
+            let step = deadline.map_or(slice, |d| slice.min(d.saturating_duration_since(time::Instant::now())));
+            match self.wait_or_timeout(Some(step), &mut checker)? {
+                WaitOutcome::Passed(state) => return Ok(state),
+                WaitOutcome::TimedOut(state) => drop(state)
+            }
+            if deadline.is_some_and(|d| time::Instant::now() >= d) { return Err(WaitObjectError::Timeout) }
+            feeder();
+        }
+    }
+
+    fn wait_sliced(&self, timeout: Option<Duration>, slice: Option<Duration>, checker: impl FnMut(&T) -> bool) -> Result<B::Guard<'_>> {
+        let (state, pass) = self.wait_core(timeout, slice, checker)?;
+        if pass { Ok(state) }
+        else { Err(WaitObjectError::Timeout) }
+    }
+
+    fn wait_core(&self, timeout: Option<Duration>, slice: Option<Duration>, mut checker: impl FnMut(&T) -> bool) -> Result<(B::Guard<'_>, bool)> {
+        let timeout = Deadline::limit(timeout)?;
+        #[cfg(feature = "stats")]
+        let (start, mut spurious) = (time::Instant::now(), 0);
+        let mut state = self.lock_state()?;
+        // every step blocks until this same instant at the latest, however many notifies do not pass the checker
+        let deadline = Deadline::from_timeout(timeout);
+        let mut continue_wait = !deadline.is_expired();
+        let mut pass = checker(&*state);
+        if !pass {
+            for _ in 0..WaitPolicy::current().spin_count {
+                if !continue_wait || pass { break }
+                drop(state);
+                std::hint::spin_loop();
+                state = self.lock_state()?;
+                continue_wait = !deadline.is_expired();
+                pass = checker(&*state);
+            }
+        }
+        let slice = match CancellationToken::in_scope() {
+            true => Some(slice.map_or(ANY_POLL_INTERVAL, |s| s.min(ANY_POLL_INTERVAL))),
+            false => slice
+        };
+        while continue_wait && !pass {
+            if CancellationToken::scope_cancelled() { return Err(WaitObjectError::Cancelled) }
+            if self.is_disconnected() { return Err(WaitObjectError::Disconnected) }
+            let wake = match slice {
+                Some(s) => deadline.min(Deadline::after(s)),
+                None => deadline
+            };
+            let notified;
+            (state, notified) = self.wait_step(state, wake)?;
+            continue_wait = !deadline.is_expired();
+            pass = checker(&*state);
+            #[cfg(feature = "stats")]
+            if notified && !pass && continue_wait { spurious += 1 }
+            #[cfg(not(feature = "stats"))]
+            let _ = notified;
+        }
+        #[cfg(feature = "stats")]
+        self.0.stats.lock()?.record(start.elapsed(), !pass, spurious);
+        Ok((state, pass))
+    }
+
+    /// Timeout of a wait given `None`: the event's default timeout, or else the one of the current [`WaitPolicy`].
+    #[inline]
+    pub(crate) fn unset_timeout(&self) -> Option<Duration> {
+        WaitPolicy::current().unset_timeout(self.0.default_timeout)
+    }
+
+    /// Timeout of a wait for several `events` given `None`: the shortest default timeout of the events, or else the
+    /// one of the current [`WaitPolicy`].
+    pub(crate) fn unset_timeout_of(events: &[&WaitEvent<T, B>]) -> Option<Duration> {
+        WaitPolicy::current().unset_timeout(events.iter().filter_map(|e| e.0.default_timeout).min())
+    }
+
+    /// Block once on the condition variable. Returns whether the wakeup came before `wake` expired.
+    fn wait_step<'a>(&'a self, state: B::Guard<'a>, wake: Deadline) -> Result<(B::Guard<'a>, bool)> {
+        #[cfg(feature = "testing")]
+        let injection = self.0.chaos.lock()?.as_mut().map(|c| c.wakeup_injection()).or_else(testing::next_injection);
+        #[cfg(feature = "testing")]
+        let state = match injection {
+            Some(injection) => {
+                drop(state);
+                std::thread::sleep(injection.delay);
+                let state = self.lock_state()?;
+                if injection.spurious { return Ok((state, true)) }
+                state
+            },
+            None => state
+        };
+        self.block(state, wake.instant())
+    }
+
+    pub fn wait_and_reset_with_waiter(&self, timeout: Option<Duration>, checker: impl FnMut(&T) -> bool, mut reset: impl FnMut() -> T) -> Result<T> {
+        let state = self.wait_for_update(timeout, checker);
+        state.map(|mut g| mem::replace(g.deref_mut(), reset()))
+    }
+
+    /// Synchronously change state of WaitObject by value
+    pub fn set_state(&mut self, new_state: T) -> Result<()> {
+        let mut state = self.lock_for_update()?;
+        *state = new_state;
+        self.commit(state);
+        Ok(())
+    }
+
+    /// Change the state, and wake waiters according to `policy`. [`NotifyPolicy::One`] avoids a thundering herd when
+    /// many threads wait for the same condition and only one of them can proceed.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use std::{thread, time::Duration};
+    /// use sync_wait_object::{NotifyPolicy, WaitEvent};
+    ///
+    /// let timeout = Some(Duration::from_secs(1));
+    /// let (mut task, done) = (WaitEvent::new_init(false), WaitEvent::new_init(0));
+    /// for _ in 0..4 {
+    ///     let (task, done) = (task.clone(), done.clone());
+    ///     thread::spawn(move || {
+    ///         task.wait_then(timeout, |t| *t, |t| *t = false).unwrap();
+    ///         done.update(|n| *n += 1).unwrap();
+    ///     });
+    /// }
+    ///
+    /// for i in 1..=4 {
+    ///     task.set_state_notify(true, NotifyPolicy::One).unwrap();
+    ///     drop(done.wait(timeout, |n| *n == i).unwrap());
+    /// }
+    /// ```
+    pub fn set_state_notify(&mut self, new_state: T, policy: NotifyPolicy) -> Result<()> {
+        let mut state = self.lock_for_update()?;
+        *state = new_state;
+        self.0.notify_with(&state, policy);
+        drop(state);
+        chain::propagate_deferred();
+        Ok(())
+    }
+
+    /// Same as [`set_state`](Self::set_state), but wakes a single waiter (see [`NotifyPolicy::One`]).
+    #[inline]
+    pub fn set_state_one(&mut self, new_state: T) -> Result<()> {
+        self.set_state_notify(new_state, NotifyPolicy::One)
+    }
+
+    /// Synchronously change state of WaitObject by a function's return value
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use std::{time::Duration, thread};
+    /// use sync_wait_object::WaitEvent;
+    ///
+    /// let wait = WaitEvent::new_init(0);
+    /// let mut w1 = wait.clone();
+    /// let mut w2 = wait.clone();
+    /// let mut w3 = wait.clone();
+    ///
+    /// thread::spawn(move || w1.set_state_func(|v| v + 1));
+    /// thread::spawn(move || w2.set_state_func(|v| v + 1));
+    /// thread::spawn(move || w3.set_state_func(|v| v + 1));
+    ///
+    /// let result = *wait.wait(Some(Duration::from_millis(200)), |v| *v == 3).unwrap();
+    /// assert_eq!(result, 3);
+    /// ```
+    pub fn set_state_func<F>(&mut self, setter: F) -> Result<()>
+    where F: FnOnce(&T) -> T
+    {
+        let mut state = self.lock_for_update()?;
+        *state = setter(&*state);
+        self.commit(state);
+        Ok(())
+    }
+
+    /// Mutate the state in place under the lock, and notify waiters. Unlike [`set_state`](Self::set_state), a large
+    /// state needs not be rebuilt for an incremental change.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use std::{thread, time::Duration};
+    /// use sync_wait_object::WaitEvent;
+    ///
+    /// let log = WaitEvent::new_init(Vec::with_capacity(1024));
+    /// let writer = log.clone();
+    /// thread::spawn(move || writer.update(|l| l.push("started")).unwrap());
+    ///
+    /// let len = log.wait_map(Some(Duration::from_secs(1)), |l| !l.is_empty(), Vec::len).unwrap();
+    /// assert_eq!(len, 1);
+    /// ```
+    #[inline]
+    pub fn update(&self, f: impl FnOnce(&mut T)) -> Result<()> {
+        self.modify(f)
+    }
+
+    /// Replace the state by `new_state` and notify waiters, only if `predicate` holds for the current state. Returns
+    /// whether the state was replaced. The check and the store happen under the same lock, which makes compare-and-set
+    /// protocols possible.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use sync_wait_object::WaitEvent;
+    ///
+    /// let owner = WaitEvent::new_init(None);
+    /// assert!(owner.set_state_if(Option::is_none, Some("worker-1")).unwrap());
+    /// assert!(!owner.set_state_if(Option::is_none, Some("worker-2")).unwrap());
+    /// assert_eq!(*owner.value().unwrap(), Some("worker-1"));
+    /// ```
+    pub fn set_state_if(&self, predicate: impl FnOnce(&T) -> bool, new_state: T) -> Result<bool> {
+        let mut state = self.lock_for_update()?;
+        if !predicate(&*state) { return Ok(false) }
+        *state = new_state;
+        self.commit(state);
+        Ok(true)
+    }
+
+    /// Replace the state by `new_state`, notify waiters, and return the old state: setters learn which value they
+    /// overwrote, without racing a separate [`value`](Self::value) call against other setters.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use std::{sync::Arc, thread};
+    /// use sync_wait_object::WaitEvent;
+    ///
+    /// let leader = Arc::new(WaitEvent::new_init(0));
+    /// let setters: Vec<_> = (1..=4).map(|id| {
+    ///     let leader = leader.clone();
+    ///     thread::spawn(move || leader.replace_state(id).unwrap())
+    /// }).collect();
+    ///
+    /// // every value but the last one was overwritten exactly once
+    /// let mut seen: Vec<_> = setters.into_iter().map(|t| t.join().unwrap()).collect();
+    /// seen.push(*leader.value().unwrap());
+    /// seen.sort();
+    /// assert_eq!(seen, [0, 1, 2, 3, 4]);
+    /// ```
+    #[inline]
+    pub fn replace_state(&self, new_state: T) -> Result<T> {
+        self.modify(|s| mem::replace(s, new_state))
+    }
+
+    /// Exchange the states of this event and `other`, and notify the waiters of both. Both locks are taken in a stable
+    /// order, so concurrent swaps never deadlock.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use sync_wait_object::WaitEvent;
+    ///
+    /// let front = WaitEvent::new_init(vec![1, 2, 3]);
+    /// let back = WaitEvent::new_init(Vec::new());
+    ///
+    /// back.swap_with(&front).unwrap();
+    /// assert_eq!(*front.value().unwrap(), vec![]);
+    /// assert_eq!(*back.value().unwrap(), vec![1, 2, 3]);
+    /// ```
+    pub fn swap_with(&self, other: &WaitEvent<T, B>) -> Result<()> {
+        if self.id() == other.id() { return Ok(()) }
+        let (first, second) = if self.id() < other.id() { (self, other) } else { (other, self) };
+        let mut a = first.lock_for_update()?;
+        let mut b = second.lock_for_update()?;
+        mem::swap(&mut *a, &mut *b);
+        first.0.notify(&a);
+        second.0.notify(&b);
+        drop((a, b));
+        chain::propagate_deferred();
+        Ok(())
+    }
+
+    /// Wait until the `checker` returns true for any of `events`, or timed-out from `timeout`. Returns the index of the
+    /// first passing event together with its lock guard.
+    ///
+    /// Only one event lock is held at any time, so this never deadlocks with other threads locking the same events in
+    /// a different order. Since every event has its own condition variable, the events are polled in turn with a
+    /// short interval ([`ANY_POLL_INTERVAL`]).
+    ///
+    /// # Arguments
+    ///
+    /// * `events` - Events to wait for
+    /// * `timeout` - Maximum wait time, `None` for the default timeout (see [`WaitPolicy`])
+    /// * `checker` - Checker function, once it returns `true` for any event, the wait ends
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use std::{thread, time::Duration};
+    /// use sync_wait_object::WaitEvent;
+    ///
+    /// let a = WaitEvent::new_init(0);
+    /// let b = WaitEvent::new_init(0);
+    /// let mut b_handle = b.clone();
+    ///
+    /// thread::spawn(move || b_handle.set_state(5).unwrap());
+    ///
+    /// let (index, value) = WaitEvent::wait_any_of(&[&a, &b], Some(Duration::from_secs(1)), |v| *v == 5).unwrap();
+    /// assert_eq!(index, 1);
+    /// assert_eq!(*value, 5);
+    /// ```
+    pub fn wait_any_of<'a>(events: &[&'a WaitEvent<T, B>], timeout: Option<Duration>, mut checker: impl FnMut(&T) -> bool) -> Result<(usize, B::Guard<'a>)> {
+        let waiter = Self::create_waiter(Deadline::limit(timeout.or_else(|| Self::unset_timeout_of(events)))?);
+        let mut turn = 0;
+        loop {
+            for (i, event) in events.iter().enumerate() {
+                let state = event.lock_state()?;
+                if checker(&*state) { return Ok((i, state)) }
+            }
+            if events.is_empty() || !waiter() { return Err(WaitObjectError::Timeout) }
+            if CancellationToken::scope_cancelled() { return Err(WaitObjectError::Cancelled) }
+
+            events[turn % events.len()].park_slice()?;
+            turn += 1;
+        }
+    }
+
+    /// Wait until the `checker` returns true for at least `n` of `events`, or timed-out from `timeout`. Returns the
+    /// indices of all passing events. Like [`wait_any_of`](Self::wait_any_of), events are polled one at a time.
+    ///
+    /// # Arguments
+    ///
+    /// * `events` - Events to wait for
+    /// * `n` - Number of events required to pass
+    /// * `timeout` - Maximum wait time, `None` for the default timeout (see [`WaitPolicy`])
+    /// * `checker` - Checker function evaluated against every event
+    pub fn wait_quorum_of(events: &[&WaitEvent<T, B>], n: usize, timeout: Option<Duration>, mut checker: impl FnMut(&T) -> bool) -> Result<Vec<usize>> {
+        let waiter = Self::create_waiter(Deadline::limit(timeout.or_else(|| Self::unset_timeout_of(events)))?);
+        let mut turn = 0;
+        loop {
+            let mut passed = Vec::with_capacity(events.len());
+            let mut pending = Vec::with_capacity(events.len());
+            for (i, event) in events.iter().enumerate() {
+                if checker(&*event.lock_state()?) { passed.push(i) } else { pending.push(i) }
+            }
+            if passed.len() >= n { return Ok(passed) }
+            if pending.is_empty() || !waiter() { return Err(WaitObjectError::Timeout) }
+            if CancellationToken::scope_cancelled() { return Err(WaitObjectError::Cancelled) }
+
+            events[pending[turn % pending.len()]].park_slice()?;
+            turn += 1;
+        }
+    }
+
+    /// Register `callback` to be called with the new state whenever a setter changes the state, until the returned
+    /// guard drops.
+    ///
+    /// The callback runs synchronously on the setter's thread while the state is locked, so it must not wait on or
+    /// set this event. To react on another thread, forward from the callback (e.g. send to a channel).
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use std::sync::{Arc, atomic::{AtomicUsize, Ordering}};
+    /// use sync_wait_object::WaitEvent;
+    ///
+    /// let mut ev = WaitEvent::new_init(0);
+    /// let last = Arc::new(AtomicUsize::new(0));
+    /// let observer = last.clone();
+    ///
+    /// let subscription = ev.on_change(move |v| observer.store(*v, Ordering::SeqCst));
+    /// ev.set_state(7).unwrap();
+    /// assert_eq!(last.load(Ordering::SeqCst), 7);
+    ///
+    /// drop(subscription);
+    /// ev.set_state(8).unwrap();
+    /// assert_eq!(last.load(Ordering::SeqCst), 7);
+    /// ```
+    pub fn on_change(&self, callback: impl Fn(&T) + Send + Sync + 'static) -> SubscriptionGuard<T> {
+        let id = self.add_listener(Arc::new(move |v| { callback(v); true }));
+        SubscriptionGuard { listeners: Arc::downgrade(&self.0.listeners), id }
+    }
+
+    /// Derive a boolean event which is kept set while `pred` holds for the state of this event, and reset otherwise.
+    /// The derived event is updated on the setter's thread (see [`on_change`](Self::on_change)) for as long as it is
+    /// referenced.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use std::time::Duration;
+    /// use sync_wait_object::{WaitEvent, SignalWaitable};
+    ///
+    /// let mut queue_len = WaitEvent::new_init(0usize);
+    /// let non_empty = queue_len.derive_signal(|len| *len > 0).unwrap();
+    /// assert!(non_empty.wait(Duration::ZERO).is_err());
+    ///
+    /// queue_len.set_state(3).unwrap();
+    /// assert!(non_empty.wait(Duration::ZERO).is_ok());
+    /// ```
+    pub fn derive_signal(&self, pred: impl Fn(&T) -> bool + Send + Sync + 'static) -> Result<ManualResetEvent> {
+        let state = self.value()?;
+        let derived = ManualResetEvent::new_init(pred(&state));
+        let target = Arc::downgrade(&derived.0.0);
+        self.add_listener(Arc::new(move |v| match target.upgrade() {
+            Some(inner) => {
+                let set = pred(v);
+                let _ = WaitEvent(inner).modify(|d| *d = set);
+                true
+            },
+            None => false
+        }));
+        Ok(derived)
+    }
+
+    fn add_listener(&self, listener: Listener<T>) -> u64 {
+        let mut listeners = self.0.listeners.lock().unwrap_or_else(|e| e.into_inner());
+        let id = listeners.next_id;
+        listeners.next_id += 1;
+        listeners.entries.push((id, listener));
+        id
+    }
+
+    /// Block on this event's condition variable for at most [`ANY_POLL_INTERVAL`].
+    fn park_slice(&self) -> Result<()> {
+        let state = self.lock_state()?;
+        drop(self.block(state, time::Instant::now().checked_add(ANY_POLL_INTERVAL))?);
+        Ok(())
+    }
+
+    /// Block on the backend until notified or `deadline`, counted as a waiter so setters know whether to notify.
+    #[inline]
+    pub(crate) fn block<'a>(&'a self, state: B::Guard<'a>, deadline: Option<time::Instant>) -> Result<(B::Guard<'a>, bool)> {
+        self.block_as(state, deadline, true)
+    }
+
+    /// Same as [`block`](Self::block), for setters waiting out a hold or a freeze, which are not waiters of a set.
+    #[inline]
+    pub(crate) fn block_setter<'a>(&'a self, state: B::Guard<'a>, deadline: Option<time::Instant>) -> Result<(B::Guard<'a>, bool)> {
+        self.block_as(state, deadline, false)
+    }
+
+    fn block_as<'a>(&'a self, state: B::Guard<'a>, deadline: Option<time::Instant>, waiter: bool) -> Result<(B::Guard<'a>, bool)> {
+        self.0.waiters.fetch_add(1, Ordering::SeqCst);
+        if !waiter { self.0.blocked_setters.fetch_add(1, Ordering::SeqCst); }
+        #[cfg(feature = "debug")]
+        let epoch = waiter.then(|| {
+            self.0.unnotified.fetch_add(1, Ordering::SeqCst);
+            self.0.notify_epoch.load(Ordering::SeqCst)
+        });
+        #[cfg(feature = "testing")]
+        testing::schedule(testing::SchedulePoint::Block, self.id());
+        let r = self.0.backend.wait_until(state, deadline);
+        // a notification has already taken this waiter off the count
+        #[cfg(feature = "debug")]
+        if epoch.is_some_and(|e| e == self.0.notify_epoch.load(Ordering::SeqCst)) {
+            self.0.unnotified.fetch_sub(1, Ordering::SeqCst);
+        }
+        #[cfg(feature = "profile")]
+        if matches!(r, Ok((_, true))) { self.0.profile.lock().unwrap_or_else(|e| e.into_inner()).record_wake() }
+        #[cfg(feature = "testing")]
+        testing::schedule(testing::SchedulePoint::Wake, self.id());
+        if !waiter { self.0.blocked_setters.fetch_sub(1, Ordering::SeqCst); }
+        self.0.waiters.fetch_sub(1, Ordering::SeqCst);
+        r
+    }
+
+    /// Lock the state.
+    pub(crate) fn lock_state(&self) -> Result<B::Guard<'_>> {
+        #[cfg(feature = "testing")]
+        testing::schedule(testing::SchedulePoint::Lock, self.id());
+        #[cfg(feature = "testing")]
+        if self.0.chaos.lock()?.as_mut().is_some_and(|c| c.poison()) { return Err(WaitObjectError::SynchronizationBroken) }
+        self.0.backend.lock()
+    }
+
+    /// Identity of the shared state, equal for all clones of this event.
+    #[inline]
+    pub(crate) fn id(&self) -> usize {
+        Arc::as_ptr(&self.0) as usize
+    }
+
+    /// Mutate the state in place under the lock and notify all waiters.
+    pub(crate) fn modify<R>(&self, f: impl FnOnce(&mut T) -> R) -> Result<R> {
+        let mut state = self.lock_for_update()?;
+        let r = f(&mut *state);
+        self.commit(state);
+        Ok(r)
+    }
+
+    /// Mutate the state in place under the lock, without notifying anyone.
+    pub(crate) fn modify_quiet<R>(&self, f: impl FnOnce(&mut T) -> R) -> Result<R> {
+        let mut state = self.lock_for_update()?;
+        Ok(f(&mut *state))
+    }
+
+    /// Notify listeners and waiters of the current state.
+    pub(crate) fn notify(&self) -> Result<()> {
+        let state = self.lock_state()?;
+        self.commit(state);
+        Ok(())
+    }
+
+    /// Notify listeners and waiters of the change made under `state`, then release the lock and apply the changes
+    /// which listeners deferred until then, such as the propagation of a [`ChainGuard`].
+    pub(crate) fn commit(&self, state: B::Guard<'_>) {
+        self.0.notify(&state);
+        drop(state);
+        chain::propagate_deferred();
+    }
+
+    fn create_waiter(timeout: Option<Duration>) -> impl Fn() -> bool {
+        let start = time::Instant::now();
+        move || {
+            match timeout {
+                Some(t) => (time::Instant::now() - start) < t,
+                None => true
+            }
+        }
+    }
+}
+
+impl<T: PartialEq, B: EventBackend<T>> WaitEvent<T, B> {
+    /// Wait until the state equals any of `accepted`, or timed-out from `timeout`. Returns the index of the matched
+    /// value in `accepted`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use std::{thread, time::Duration};
+    /// use sync_wait_object::WaitEvent;
+    ///
+    /// #[derive(PartialEq)]
+    /// enum Phase { Starting, Completed, Cancelled, Failed }
+    ///
+    /// let phase = WaitEvent::new_init(Phase::Starting);
+    /// let mut setter = phase.clone();
+    /// thread::spawn(move || setter.set_state(Phase::Cancelled).unwrap());
+    ///
+    /// let terminal = [Phase::Completed, Phase::Cancelled, Phase::Failed];
+    /// assert_eq!(phase.wait_for_any(&terminal, Some(Duration::from_secs(1))).unwrap(), 1);
+    /// ```
+    pub fn wait_for_any(&self, accepted: &[T], timeout: Option<Duration>) -> Result<usize> {
+        let mut matched = 0;
+        drop(self.wait(timeout, |v| match accepted.iter().position(|a| a == v) {
+            Some(i) => { matched = i; true },
+            None => false
+        })?);
+        Ok(matched)
+    }
+}
+
+impl<T, B: EventBackend<T> + NonPoisoning> WaitEvent<T, B> {
+    /// Same as [`value`](Self::value), for backends which cannot be poisoned.
+    #[inline]
+    pub fn value_infallible(&self) -> B::Guard<'_> {
+        infallible(self.value()).expect("locking never fails")
+    }
+
+    /// Same as [`wait`](Self::wait), for backends which cannot be poisoned. Returns `None` when the wait ends without
+    /// the `checker` passing (timeout, cancellation, or disconnection).
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # #[cfg(feature = "parking_lot")] {
+    /// # use std::time::Duration;
+    /// use sync_wait_object::{WaitEvent, ParkingLotBackend};
+    ///
+    /// let mut ev = WaitEvent::<_, ParkingLotBackend<_>>::with_backend(0);
+    /// ev.set_state_infallible(1);
+    /// assert_eq!(ev.wait_infallible(Some(Duration::ZERO), |v| *v == 1).map(|v| *v), Some(1));
+    /// assert!(ev.wait_infallible(Some(Duration::ZERO), |v| *v == 2).is_none());
+    /// # }
+    /// ```
+    #[inline]
+    pub fn wait_infallible(&self, timeout: Option<Duration>, checker: impl FnMut(&T) -> bool) -> Option<B::Guard<'_>> {
+        infallible(self.wait(timeout, checker))
+    }
+
+    /// Same as [`set_state`](Self::set_state), for backends which cannot be poisoned.
+    #[inline]
+    pub fn set_state_infallible(&mut self, new_state: T) {
+        infallible(self.set_state(new_state));
+    }
+}
+
+impl<T: Default, B: EventBackend<T>> WaitEvent<T, B> {
+    /// Take the state out, leaving the default value, and notify waiters.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use std::thread;
+    /// use sync_wait_object::WaitEvent;
+    ///
+    /// let mut mailbox = WaitEvent::new_init(None);
+    /// let mut sender = mailbox.clone();
+    ///
+    /// thread::spawn(move || sender.set_state(Some("hello")).unwrap());
+    /// drop(mailbox.wait(None, Option::is_some).unwrap());
+    /// assert_eq!(mailbox.take().unwrap(), Some("hello"));
+    /// assert_eq!(*mailbox.value().unwrap(), None);
+    /// ```
+    #[inline]
+    pub fn take(&mut self) -> Result<T> {
+        self.modify(mem::take)
+    }
+}
+
+impl<V, B: EventBackend<Option<V>>> WaitEvent<Option<V>, B> {
+    /// Wait until the state is `Some`, or timed-out from `timeout`, then take the value out, leaving `None`, and notify
+    /// waiters, all under the same lock. Turns the event into a one-slot mailbox.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use std::{thread, time::Duration};
+    /// use sync_wait_object::WaitEvent;
+    ///
+    /// let mailbox = WaitEvent::new_init(None);
+    /// let mut sender = mailbox.clone();
+    ///
+    /// thread::spawn(move || sender.set_state(Some("hello")).unwrap());
+    /// assert_eq!(mailbox.wait_take(Some(Duration::from_secs(1))).unwrap(), "hello");
+    /// assert_eq!(*mailbox.value().unwrap(), None);
+    /// ```
+    pub fn wait_take(&self, timeout: Option<Duration>) -> Result<V> {
+        self.wait_then(timeout, Option::is_some, |v| v.take().expect("checked to be Some"))
+    }
+}
+
+impl<T: Clone, B: EventBackend<T>> WaitEvent<T, B> {
+    /// Copy of the current state. Unlike [`value`](Self::value), the lock is released before returning.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use sync_wait_object::WaitEvent;
+    ///
+    /// let mut ev = WaitEvent::new_init(vec![1, 2]);
+    /// let snapshot = ev.peek().unwrap();
+    /// ev.set_state(vec![3]).unwrap();
+    /// assert_eq!(snapshot, vec![1, 2]);
+    /// ```
+    #[inline]
+    pub fn peek(&self) -> Result<T> {
+        Ok(self.lock_state()?.clone())
+    }
+
+    /// Wait until the `checker` returns true, or timed-out from `timeout`, and return a copy of the state. Unlike
+    /// [`wait`](Self::wait), the lock is released before returning, so the value can be processed at leisure.
+    ///
+    /// # Arguments
+    ///
+    /// * `timeout` - Maximum wait time, `None` for the default timeout (see [`WaitPolicy`])
+    /// * `checker` - Checker function, once it returns `true`, the wait ends
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use std::{thread, time::Duration};
+    /// use sync_wait_object::WaitEvent;
+    ///
+    /// let config = WaitEvent::new_init(Vec::<String>::new());
+    /// let mut loader = config.clone();
+    /// thread::spawn(move || loader.set_state(vec!["verbose".to_string()]).unwrap());
+    ///
+    /// let options = config.wait_cloned(Some(Duration::from_secs(1)), |c| !c.is_empty()).unwrap();
+    /// assert_eq!(options, ["verbose"]);
+    /// ```
+    #[inline]
+    pub fn wait_cloned(&self, timeout: Option<Duration>, checker: impl FnMut(&T) -> bool) -> Result<T> {
+        self.wait_map(timeout, checker, T::clone)
+    }
+
+    /// Mirror of [`AtomicUsize::fetch_update`](std::sync::atomic::AtomicUsize::fetch_update) under the state lock:
+    /// `f` returns the new state, or `None` to leave it unchanged. Returns `Ok` with the previous state if it was
+    /// replaced, which notifies waiters, or `Err` with a copy of the current state otherwise. Since the lock is held, `f`
+    /// runs exactly once.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use sync_wait_object::WaitEvent;
+    ///
+    /// let slots = WaitEvent::new_init(2u32);
+    /// let acquire = || slots.fetch_update(|n| n.checked_sub(1)).unwrap();
+    ///
+    /// assert_eq!(acquire(), Ok(2));
+    /// assert_eq!(acquire(), Ok(1));
+    /// assert_eq!(acquire(), Err(0));
+    /// ```
+    pub fn fetch_update(&self, mut f: impl FnMut(&T) -> Option<T>) -> Result<std::result::Result<T, T>> {
+        let mut state = self.lock_for_update()?;
+        match f(&*state) {
+            Some(next) => {
+                let previous = mem::replace(state.deref_mut(), next);
+                self.commit(state);
+                Ok(Ok(previous))
+            },
+            None => Ok(Err((*state).clone()))
+        }
+    }
+
+    /// Same as [`update`](Self::update), and return a copy of the updated state.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use sync_wait_object::WaitEvent;
+    ///
+    /// let counters = WaitEvent::new_init([0u32; 4]);
+    /// assert_eq!(counters.update_and_get(|c| c[2] += 1).unwrap(), [0, 0, 1, 0]);
+    /// ```
+    pub fn update_and_get(&self, f: impl FnOnce(&mut T)) -> Result<T> {
+        self.modify(|s| {
+            f(s);
+            s.clone()
+        })
+    }
+
+    /// Create a new independent event initialized with a copy of the current state, and the same default timeout and
+    /// name. Changes to either event are not seen by the waiters of the other.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use sync_wait_object::WaitEvent;
+    ///
+    /// let parent = WaitEvent::new_init(1);
+    /// let mut island = parent.split_off().unwrap();
+    ///
+    /// island.set_state(2).unwrap();
+    /// assert_eq!(*parent.value().unwrap(), 1);
+    /// assert_eq!(*island.value().unwrap(), 2);
+    /// ```
+    pub fn split_off(&self) -> Result<Self> {
+        let state = self.peek()?;
+        Ok(Self::with_config(state, self.0.default_timeout, self.0.name.clone()))
+    }
+}
+
+impl<T: Clone + PartialEq, B: EventBackend<T>> WaitEvent<T, B> {
+    /// Same as [`wait_status`](Self::wait_status), but a timeout also tells whether the state changed during the wait,
+    /// i.e. whether progress was being made too slowly, or nothing happened at all.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use std::{thread, time::Duration};
+    /// use sync_wait_object::{WaitEvent, TimeoutResult};
+    ///
+    /// let progress = WaitEvent::new_init(0);
+    /// let mut worker = progress.clone();
+    /// thread::spawn(move || {
+    ///     thread::sleep(Duration::from_millis(20));
+    ///     worker.set_state(50).unwrap();
+    /// });
+    ///
+    /// let r = progress.wait_timeout_result(Some(Duration::from_millis(100)), |v| *v == 100).unwrap();
+    /// assert!(matches!(r, TimeoutResult::TimedOut { progressed: true }));
+    /// ```
+    pub fn wait_timeout_result(&self, timeout: Option<Duration>, mut checker: impl FnMut(&T) -> bool) -> Result<TimeoutResult<B::Guard<'_>>> {
+        let mut initial: Option<T> = None;
+        let mut progressed = false;
+        let r = self.wait(timeout, |v| {
+            match &initial {
+                None => initial = Some(v.clone()),
+                Some(i) => progressed |= i != v
             }
+            checker(v)
+        });
+        match r {
+            Ok(g) => Ok(TimeoutResult::Signaled(g)),
+            Err(WaitObjectError::Timeout) => Ok(TimeoutResult::TimedOut { progressed }),
+            Err(e) => Err(e)
         }
     }
 }
@@ -230,6 +1711,163 @@ impl ManualResetEvent {
     pub fn new_init(initial_state: bool) -> Self {
         Self(WaitEvent::new_init(initial_state))
     }
+
+    /// Wait until the event is set, reporting a timeout as [`WaitStatus::TimedOut`] instead of an error.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use std::time::Duration;
+    /// use sync_wait_object::ManualResetEvent;
+    ///
+    /// let ev = ManualResetEvent::new();
+    /// assert!(ev.wait_status(Some(Duration::ZERO)).unwrap().is_timed_out());
+    /// ```
+    #[inline]
+    pub fn wait_status(&self, timeout: Option<Duration>) -> Result<WaitStatus<()>> {
+        WaitStatus::from_result(self.0.wait(timeout, |v| *v).map(|_| ()))
+    }
+
+    /// Whether the event is currently set, without waiting.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use sync_wait_object::{ManualResetEvent, SignalWaitable};
+    ///
+    /// let mut ev = ManualResetEvent::new();
+    /// assert!(!ev.is_set().unwrap());
+    /// ev.set().unwrap();
+    /// assert!(ev.is_set().unwrap());
+    /// ```
+    #[inline]
+    pub fn is_set(&self) -> Result<bool> { Ok(*self.0.value()?) }
+
+    /// Number of threads currently blocked waiting on this event, see [`WaitEvent::waiter_count`].
+    #[inline]
+    pub fn waiter_count(&self) -> usize { self.0.waiter_count() }
+
+    /// Whether any thread is currently blocked waiting on this event, see [`WaitEvent::has_waiters`].
+    #[inline]
+    pub fn has_waiters(&self) -> bool { self.0.has_waiters() }
+
+    /// Wait until at least `n` of `events` are set, or timed-out from `timeout`. Returns the indices of the set events.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use std::{thread, time::Duration};
+    /// use sync_wait_object::{ManualResetEvent, SignalWaitable};
+    ///
+    /// let acks: Vec<_> = (0..3).map(|_| ManualResetEvent::new()).collect();
+    /// for i in [0, 2] {
+    ///     let mut ack = acks[i].clone();
+    ///     thread::spawn(move || ack.set().unwrap());
+    /// }
+    ///
+    /// let refs: Vec<_> = acks.iter().collect();
+    /// let set = ManualResetEvent::wait_quorum(&refs, 2, Some(Duration::from_secs(1))).unwrap();
+    /// assert_eq!(set, vec![0, 2]);
+    /// ```
+    pub fn wait_quorum(events: &[&ManualResetEvent], n: usize, timeout: Option<Duration>) -> Result<Vec<usize>> {
+        let events: Vec<_> = events.iter().map(|e| &e.0).collect();
+        WaitEvent::wait_quorum_of(&events, n, timeout, |v| *v)
+    }
+
+    /// Create `n` independent auto-reset handles, each signaled by every later set of this event, so several consumers
+    /// can react to the same signal without stealing each other's wakeups. A handle stops following the event once all
+    /// its clones drop.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use std::time::Duration;
+    /// use sync_wait_object::{ManualResetEvent, SignalWaitable};
+    ///
+    /// let mut reload = ManualResetEvent::new();
+    /// let handles = reload.tee(2);
+    ///
+    /// reload.set().unwrap();
+    /// for h in &handles {
+    ///     assert!(h.wait(Duration::from_secs(1)).is_ok());
+    ///     assert!(h.wait(Duration::from_millis(10)).is_err());
+    /// }
+    /// ```
+    pub fn tee(&self, n: usize) -> Vec<AutoResetEvent> {
+        (0..n).map(|_| {
+            let handle = AutoResetEvent::new();
+            let target = Arc::downgrade(&handle.0.0);
+            self.0.add_listener(Arc::new(move |v| match target.upgrade() {
+                Some(inner) => {
+                    if *v { let _ = WaitEvent(inner).modify(|d| *d = true); }
+                    true
+                },
+                None => false
+            }));
+            handle
+        }).collect()
+    }
+
+    /// Register `callback` to be called on the setter's thread whenever the event is set. See
+    /// [`WaitEvent::on_change`].
+    pub fn on_set(&self, callback: impl Fn() + Send + Sync + 'static) -> SubscriptionGuard<bool> {
+        self.0.on_change(move |v| if *v { callback() })
+    }
+
+    /// Set the event, and reset it when the returned [`SetGuard`] drops (including on panic).
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use std::time::Duration;
+    /// use sync_wait_object::{ManualResetEvent, SignalWaitable};
+    ///
+    /// let mut busy = ManualResetEvent::new();
+    /// {
+    ///     let _guard = busy.set_scoped().unwrap();
+    ///     assert!(busy.wait(Duration::ZERO).is_ok());
+    /// }
+    /// assert!(busy.wait(Duration::ZERO).is_err());
+    /// ```
+    pub fn set_scoped(&mut self) -> Result<SetGuard> {
+        self.set()?;
+        Ok(SetGuard(self.clone()))
+    }
+
+    /// Guard which sets the event when dropped, including on panic, so a worker signals "done or died".
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use std::{thread, time::Duration};
+    /// use sync_wait_object::{ManualResetEvent, SignalWaitable};
+    ///
+    /// let finished = ManualResetEvent::new();
+    /// let guard = finished.completion_guard();
+    ///
+    /// thread::spawn(move || {
+    ///     let _guard = guard;
+    ///     panic!("worker failed");
+    /// });
+    ///
+    /// assert!(finished.wait(Duration::from_secs(1)).is_ok());
+    /// ```
+    #[inline]
+    pub fn completion_guard(&self) -> CompletionGuard {
+        CompletionGuard(self.clone())
+    }
+}
+
+impl Drop for SetGuard {
+    fn drop(&mut self) {
+        let _ = self.0.reset();
+    }
+}
+
+impl Drop for CompletionGuard {
+    fn drop(&mut self) {
+        let _ = self.0.set();
+    }
 }
 
 impl SignalWaitable for ManualResetEvent {
@@ -241,22 +1879,362 @@ impl SignalWaitable for ManualResetEvent {
     #[inline] fn reset(&mut self) -> Result<()> {
         self.0.set_state(false)
     }
+    #[inline] fn is_set(&self) -> Result<bool> { ManualResetEvent::is_set(self) }
 }
 
 impl AutoResetEvent {
     #[inline] pub fn new() -> Self { Self::new_init(false) }
-    #[inline] pub fn new_init(initial_state: bool) -> Self { Self(WaitEvent::new_init(initial_state)) }
+    #[inline] pub fn new_init(initial_state: bool) -> Self { WaitEvent::new_init(initial_state).into() }
+
+    /// Wait until the event is set and reset it, reporting a timeout as [`WaitStatus::TimedOut`] instead of an error.
+    #[inline] pub fn wait_status(&self, timeout: Option<Duration>) -> Result<WaitStatus<()>> {
+        WaitStatus::from_result(self.acquire(timeout))
+    }
+
+    /// Whether the event is currently set and not claimed by a waiter, without waiting nor consuming the signal.
+    #[inline] pub fn is_set(&self) -> Result<bool> { Ok(*self.0.value()? && !self.0.0.claimed.load(Ordering::Acquire)) }
+
+    /// Number of threads currently blocked waiting on this event, see [`WaitEvent::waiter_count`].
+    #[inline] pub fn waiter_count(&self) -> usize { self.0.waiter_count() }
+
+    /// Whether any thread is currently blocked waiting on this event, see [`WaitEvent::has_waiters`].
+    #[inline] pub fn has_waiters(&self) -> bool { self.0.has_waiters() }
+
+    /// Wait until the event is set, then run `f` while the signal is still claimed. No other waiter is released until
+    /// `f` completes, like with [`wait_guarded`](Self::wait_guarded). `f` runs without the state lock, so it may set
+    /// the event itself; such a set is kept pending until `f` completes.
+    ///
+    /// For states richer than `bool`, see [`WaitEvent::wait_reset_with`].
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use std::time::Duration;
+    /// use sync_wait_object::{AutoResetEvent, SignalWaitable};
+    ///
+    /// let ev = AutoResetEvent::new_init(true);
+    /// let r = ev.wait_and_then(Some(Duration::from_secs(1)), || 42).unwrap();
+    /// assert_eq!(r, 42);
+    /// assert!(ev.wait(Duration::from_millis(10)).is_err());
+    ///
+    /// let mut again = ev.clone();
+    /// again.set().unwrap();
+    /// ev.wait_and_then(Some(Duration::from_secs(1)), || again.set().unwrap()).unwrap();
+    /// assert!(ev.wait(Duration::from_millis(10)).is_ok());
+    /// ```
+    pub fn wait_and_then<R>(&self, timeout: Option<Duration>, f: impl FnOnce() -> R) -> Result<R> {
+        let processing = self.wait_guarded(timeout)?;
+        let r = f();
+        drop(processing);
+        Ok(r)
+    }
+
+    /// Wait until the event is set and defer the reset to the returned [`ResetGuard`]. The event stays set (and locked)
+    /// until the guard drops.
+    pub fn wait_deferred(&self, timeout: Option<Duration>) -> Result<ResetGuard<'_>> {
+        self.0.wait_for_update(timeout.or_else(|| self.0.unset_timeout()), self.signaled()).map(|state| ResetGuard(Some(state), self))
+    }
+
+    /// Wait until the event is set, consume the signal, and keep the event claimed until the returned
+    /// [`ProcessingGuard`] drops. Sets received while the guard is alive are kept pending: no other waiter is released
+    /// by them until the guard drops, at which point the event is signalled again.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use std::time::Duration;
+    /// use sync_wait_object::{AutoResetEvent, SignalWaitable};
+    ///
+    /// let mut ev = AutoResetEvent::new_init(true);
+    /// let guard = ev.wait_guarded(Some(Duration::from_secs(1))).unwrap();
+    ///
+    /// ev.set().unwrap();
+    /// assert!(ev.wait(Duration::from_millis(10)).is_err());
+    ///
+    /// drop(guard);
+    /// assert!(ev.wait(Duration::from_millis(10)).is_ok());
+    /// ```
+    ///
+    /// The claim is shared by all handles of the event, including those converted from the same [`WaitEvent`]:
+    ///
+    /// ```rust
+    /// # use std::time::Duration;
+    /// use sync_wait_object::{AutoResetEvent, SignalWaitable, WaitEvent};
+    ///
+    /// let state = WaitEvent::new_init(true);
+    /// let (ev, mut other) = (AutoResetEvent::from(state.clone()), AutoResetEvent::from(state));
+    /// let guard = ev.wait_guarded(Some(Duration::from_secs(1))).unwrap();
+    ///
+    /// other.set().unwrap();
+    /// assert!(other.wait(Duration::from_millis(10)).is_err());
+    ///
+    /// drop(guard);
+    /// assert!(other.wait(Duration::from_millis(10)).is_ok());
+    /// ```
+    pub fn wait_guarded(&self, timeout: Option<Duration>) -> Result<ProcessingGuard> {
+        self.0.wait_then(timeout, self.signaled(), |state| {
+            self.claim(state);
+            self.0.0.claimed.store(true, Ordering::Release);
+        })?;
+        Ok(ProcessingGuard(self.clone()))
+    }
+
+    /// Admit exactly `n` of the currently blocked waiters, without setting the event. If fewer threads are blocked,
+    /// only those are admitted. A released permit may be taken by a thread starting to wait before a blocked one wakes
+    /// up, but never more than `n` waiters pass in total.
+    ///
+    /// Returns the number of waiters admitted.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use std::{thread, time::Duration};
+    /// use sync_wait_object::{AutoResetEvent, SignalWaitable};
+    ///
+    /// let mut gate = AutoResetEvent::new();
+    /// let workers: Vec<_> = (0..3).map(|_| {
+    ///     let gate = gate.clone();
+    ///     thread::spawn(move || gate.wait(Duration::from_millis(300)).is_ok())
+    /// }).collect();
+    ///
+    /// while gate.waiter_count() < 3 { thread::yield_now() }
+    /// assert_eq!(gate.release(2).unwrap(), 2);
+    ///
+    /// let admitted = workers.into_iter().map(|w| w.join().unwrap()).filter(|ok| *ok).count();
+    /// assert_eq!(admitted, 2);
+    /// ```
+    pub fn release(&mut self, n: usize) -> Result<usize> {
+        let (event, permits) = (&self.0, &self.0.0.permits);
+        event.modify(|_| {
+            let admitted = n.min(event.waiter_count().saturating_sub(permits.load(Ordering::Acquire)));
+            permits.fetch_add(admitted, Ordering::AcqRel);
+            admitted
+        })
+    }
+
+    /// Wait until the event is set or a released permit is available, then consume it.
+    fn acquire(&self, timeout: Option<Duration>) -> Result<()> {
+        self.0.wait_then(timeout, self.signaled(), |state| self.claim(state))
+    }
+
+    /// Consume the signal that admitted a waiter: the set state first, otherwise a released permit.
+    fn claim(&self, state: &mut bool) {
+        if *state && !self.0.0.claimed.load(Ordering::Acquire) {
+            *state = false;
+        } else {
+            self.0.0.permits.fetch_sub(1, Ordering::AcqRel);
+        }
+    }
+
+    fn signaled(&self) -> impl Fn(&bool) -> bool + '_ {
+        move |v| (*v && !self.0.0.claimed.load(Ordering::Acquire)) || self.0.0.permits.load(Ordering::Acquire) > 0
+    }
+}
+
+impl Drop for ProcessingGuard {
+    fn drop(&mut self) {
+        let claimed = &self.0.0.0.claimed;
+        let _ = self.0.0.modify(|_| claimed.store(false, Ordering::Release));
+    }
+}
+
+impl Drop for ResetGuard<'_> {
+    fn drop(&mut self) {
+        if let Some(mut state) = self.0.take() {
+            self.1.claim(&mut state);
+            self.1.0.commit(state);
+        }
+    }
 }
 
 impl SignalWaitable for AutoResetEvent {
-    #[inline] fn wait_until_set(&self) -> Result<()> { self.0.wait_reset(None, || false, |v| *v).map(|_| ()) }
-    #[inline] fn wait(&self, timeout: Duration) -> Result<()> { self.0.wait_reset(Some(timeout), || false, |v| *v).map(|_| ()) }
+    #[inline] fn wait_until_set(&self) -> Result<()> { self.acquire(None) }
+    #[inline] fn wait(&self, timeout: Duration) -> Result<()> { self.acquire(Some(timeout)) }
     #[inline] fn set(&mut self) -> Result<()> {
         self.0.set_state(true)
     }
     #[inline] fn reset(&mut self) -> Result<()> {
         self.0.set_state(false)
     }
+    #[inline] fn is_set(&self) -> Result<bool> { AutoResetEvent::is_set(self) }
+}
+
+impl<T, B: EventBackend<T>> Inner<T, B> {
+    /// Run the change listeners with the new `state`, and wake all waiters if any. Must be called under the state lock,
+    /// which waiters also hold when they register.
+    #[inline]
+    fn notify(&self, state: &T) {
+        self.notify_with(state, NotifyPolicy::All)
+    }
+
+    fn notify_with(&self, state: &T, policy: NotifyPolicy) {
+        #[cfg(feature = "testing")]
+        testing::schedule(testing::SchedulePoint::Notify, self as *const Self as usize);
+        let listeners: Vec<_> = self.listeners.lock().unwrap_or_else(|e| e.into_inner())
+            .entries.clone();
+        let expired: Vec<_> = listeners.into_iter().filter_map(|(id, l)| (!l(state)).then_some(id)).collect();
+        if !expired.is_empty() {
+            self.listeners.lock().unwrap_or_else(|e| e.into_inner()).entries.retain(|(id, _)| !expired.contains(id));
+        }
+        #[cfg(feature = "profile")]
+        self.profile.lock().unwrap_or_else(|e| e.into_inner()).record_set();
+        #[cfg(feature = "testing")]
+        if let Some(delay) = self.chaos.lock().unwrap_or_else(|e| e.into_inner()).as_mut().map(|c| c.notify_delay()) {
+            std::thread::sleep(delay);
+        }
+        if self.waiters.load(Ordering::SeqCst) > 0 {
+            // a single wake-up may reach any waiter, so no waiter is counted as abandoned either way
+            #[cfg(feature = "debug")]
+            {
+                self.notify_epoch.fetch_add(1, Ordering::SeqCst);
+                self.unnotified.store(0, Ordering::SeqCst);
+            }
+            match policy {
+                NotifyPolicy::All => self.backend.notify_all(),
+                NotifyPolicy::One => self.backend.notify_one()
+            }
+        }
+    }
+}
+
+impl<T> Drop for SubscriptionGuard<T> {
+    fn drop(&mut self) {
+        if let Some(listeners) = self.listeners.upgrade() {
+            let mut listeners = listeners.lock().unwrap_or_else(|e| e.into_inner());
+            listeners.entries.retain(|(id, _)| *id != self.id);
+        }
+    }
+}
+
+impl<'a, T, B: EventBackend<T>> WaitOutcome<'a, T, B> {
+    #[inline]
+    pub fn is_timed_out(&self) -> bool { matches!(self, Self::TimedOut(_)) }
+
+    #[inline]
+    pub fn into_guard(self) -> B::Guard<'a> {
+        match self {
+            Self::Passed(g) | Self::TimedOut(g) => g
+        }
+    }
+}
+
+impl WaitCtx {
+    /// Deadline of the wait.
+    #[inline]
+    pub fn deadline(&self) -> Deadline { self.deadline }
+
+    /// Time left before the wait times out, `None` if it never does.
+    #[inline]
+    pub fn remaining(&self) -> Option<Duration> { self.deadline.remaining() }
+
+    /// Number of times the checker was evaluated before, i.e. `0` on the first evaluation, then one per wake-up.
+    #[inline]
+    pub fn wakeups(&self) -> usize { self.wakeups }
+}
+
+impl<V> Traced<V> {
+    /// Number of checker evaluations.
+    #[inline]
+    pub fn evaluations(&self) -> usize { self.checks.len() }
+
+    /// Total time spent in the checker.
+    #[inline]
+    pub fn total(&self) -> Duration { self.checks.iter().sum() }
+
+    /// Longest checker evaluation.
+    #[inline]
+    pub fn slowest(&self) -> Duration { self.checks.iter().copied().max().unwrap_or_default() }
+}
+
+impl<G> WaitStatus<G> {
+    #[inline]
+    pub fn is_signaled(&self) -> bool { matches!(self, Self::Signaled(_)) }
+
+    #[inline]
+    pub fn is_timed_out(&self) -> bool { matches!(self, Self::TimedOut) }
+
+    /// The payload of [`Signaled`](Self::Signaled), if any.
+    #[inline]
+    pub fn signaled(self) -> Option<G> {
+        match self {
+            Self::Signaled(g) => Some(g),
+            Self::TimedOut => None
+        }
+    }
+
+    /// Convert a wait result, turning [`WaitObjectError::Timeout`] into [`TimedOut`](Self::TimedOut).
+    pub fn from_result(r: Result<G>) -> Result<Self> {
+        match r {
+            Ok(g) => Ok(Self::Signaled(g)),
+            Err(WaitObjectError::Timeout) => Ok(Self::TimedOut),
+            Err(e) => Err(e)
+        }
+    }
+}
+
+impl<T, B> Clone for WaitEvent<T, B> {
+    fn clone(&self) -> Self {
+        Self(self.0.clone())
+    }
+}
+
+#[cfg(feature = "debug")]
+impl<T, B> Drop for WaitEvent<T, B> {
+    fn drop(&mut self) {
+        let waiters = self.0.unnotified.load(Ordering::SeqCst);
+        if waiters > 0 && Arc::strong_count(&self.0) - 1 <= waiters && !std::thread::panicking() {
+            debug::report_abandoned(self.0.name.as_deref(), waiters);
+        }
+    }
+}
+
+impl Default for ManualResetEvent {
+    #[inline] fn default() -> Self { Self::new() }
+}
+
+impl Default for AutoResetEvent {
+    #[inline] fn default() -> Self { Self::new() }
+}
+
+impl WaitObjectError {
+    #[inline]
+    pub fn is_timeout(&self) -> bool { matches!(self, Self::Timeout) }
+
+    /// Whether the sync object is broken (poisoned) by a panic.
+    #[inline]
+    pub fn is_poisoned(&self) -> bool { matches!(self, Self::SynchronizationBroken) }
+
+    #[inline]
+    pub fn is_os(&self) -> bool { matches!(self, Self::OsError(..)) }
+
+    /// OS error code of an [`OsError`](Self::OsError).
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use sync_wait_object::WaitObjectError;
+    ///
+    /// let e = WaitObjectError::OsError(5, "Access is denied.".to_string());
+    /// assert!(e.is_os() && !e.is_timeout());
+    /// assert_eq!(e.os_code(), Some(5));
+    /// assert_eq!(WaitObjectError::Timeout.os_code(), None);
+    /// ```
+    #[inline]
+    pub fn os_code(&self) -> Option<isize> {
+        match self {
+            Self::OsError(code, _) => Some(*code),
+            _ => None
+        }
+    }
+}
+
+/// Result of an operation on a [`NonPoisoning`] backend, where the lock cannot be broken.
+fn infallible<R>(r: Result<R>) -> Option<R> {
+    match r {
+        Ok(v) => Some(v),
+        Err(WaitObjectError::SynchronizationBroken) => unreachable!("non-poisoning backend reported a broken lock"),
+        Err(_) => None
+    }
 }
 
 impl<T> From<std::sync::PoisonError<T>> for WaitObjectError {
@@ -279,8 +2257,8 @@ impl From<ManualResetEvent> for WaitEvent<bool> {
 
 impl From<WaitEvent<bool>> for AutoResetEvent {
     fn from(value: WaitEvent<bool>) -> Self {
-                                          Self(value)
-                                                     }
+        Self(value)
+    }
 }
 
 impl From<AutoResetEvent> for WaitEvent<bool> {
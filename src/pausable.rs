@@ -0,0 +1,85 @@
+use std::{ collections::VecDeque, sync::{ Arc, Mutex } };
+use crate::{ WaitEvent, Result };
+
+// ------------------------------ DATA TYPES ----------------------------------
+/// Setter of an event which can be paused: states set while paused are buffered, and delivered in order on
+/// [`resume`](Pausable::resume). Clones share the pause state.
+///
+/// # Examples
+///
+/// ```rust
+/// # use std::time::Duration;
+/// use sync_wait_object::{Pausable, WaitEvent};
+///
+/// let config = WaitEvent::new_init(1);
+/// let mut setter = Pausable::new(config.clone());
+///
+/// setter.pause().unwrap();
+/// setter.set_state(2).unwrap();
+/// assert_eq!(*config.value().unwrap(), 1);
+///
+/// setter.resume().unwrap();
+/// assert_eq!(*config.wait(Some(Duration::from_secs(1)), |v| *v == 2).unwrap(), 2);
+/// ```
+pub struct Pausable<T> {
+    event: WaitEvent<T>,
+    state: Arc<Mutex<PauseState<T>>>
+}
+
+struct PauseState<T> {
+    paused: bool,
+    pending: VecDeque<T>
+}
+
+// ------------------------------ IMPLEMENTATIONS ------------------------------
+impl<T> Pausable<T> {
+    /// Wrap `event`, initially not paused.
+    pub fn new(event: WaitEvent<T>) -> Self {
+        Self { event, state: Arc::new(Mutex::new(PauseState { paused: false, pending: VecDeque::new() })) }
+    }
+
+    #[inline]
+    pub fn event(&self) -> &WaitEvent<T> { &self.event }
+
+    pub fn is_paused(&self) -> Result<bool> {
+        Ok(self.state.lock()?.paused)
+    }
+
+    /// Set the state of the event, or buffer it while paused.
+    pub fn set_state(&mut self, new_state: T) -> Result<()> {
+        let mut state = self.state.lock()?;
+        if state.paused {
+            state.pending.push_back(new_state);
+            Ok(())
+        } else {
+            self.event.set_state(new_state)
+        }
+    }
+
+    /// Start buffering the states set through this wrapper.
+    pub fn pause(&self) -> Result<()> {
+        self.state.lock()?.paused = true;
+        Ok(())
+    }
+
+    /// Deliver the buffered states in order, and stop buffering.
+    pub fn resume(&mut self) -> Result<()> {
+        let mut state = self.state.lock()?;
+        while let Some(next) = state.pending.pop_front() {
+            self.event.set_state(next)?;
+        }
+        state.paused = false;
+        Ok(())
+    }
+
+    /// Number of states buffered while paused.
+    pub fn pending(&self) -> Result<usize> {
+        Ok(self.state.lock()?.pending.len())
+    }
+}
+
+impl<T> Clone for Pausable<T> {
+    fn clone(&self) -> Self {
+        Self { event: self.event.clone(), state: self.state.clone() }
+    }
+}
@@ -0,0 +1,67 @@
+use std::time::{ Duration, Instant };
+use crate::{ WaitEvent, Result, WaitObjectError, timer::TimerCheck };
+
+// ------------------------------ DATA TYPES ----------------------------------
+/// Gate which admits at most one caller per period: each [`pass`](PeriodGate::pass) opens the next slot one period
+/// after itself, and the other callers block until then. Unlike a token bucket, bursts are never allowed, so passes are
+/// always at least one period apart. Clones share the same gate.
+///
+/// # Examples
+///
+/// ```rust
+/// # use std::time::{Duration, Instant};
+/// use sync_wait_object::PeriodGate;
+///
+/// let gate = PeriodGate::new(Duration::from_millis(30));
+/// let start = Instant::now();
+///
+/// gate.pass(None).unwrap();
+/// assert!(!gate.try_pass().unwrap());
+///
+/// gate.pass(Some(Duration::from_secs(1))).unwrap();
+/// assert!(start.elapsed() >= Duration::from_millis(30));
+/// ```
+#[derive(Clone)]
+pub struct PeriodGate {
+    next_slot: WaitEvent<Option<Instant>>,
+    period: Duration
+}
+
+// ------------------------------ IMPLEMENTATIONS ------------------------------
+impl PeriodGate {
+    /// Create a gate whose first slot is open.
+    pub fn new(period: Duration) -> Self {
+        Self { next_slot: WaitEvent::new_init(None), period }
+    }
+
+    #[inline]
+    pub fn period(&self) -> Duration { self.period }
+
+    /// Wait until the next slot opens, or timed-out from `timeout`, and take it.
+    ///
+    /// # Arguments
+    ///
+    /// * `timeout` - Maximum wait time, `None` to wait forever
+    pub fn pass(&self, timeout: Option<Duration>) -> Result<()> {
+        let mut passed_at = Instant::now();
+        let mut next_slot = self.next_slot.wait_timer_for_update(timeout, |next, now| {
+            passed_at = now;
+            match next {
+                Some(at) if now < *at => TimerCheck::Pending(Some(*at)),
+                _ => TimerCheck::Ready
+            }
+        })?;
+        // the slot only moves later, so the other callers need no notification
+        *next_slot = Some(passed_at + self.period);
+        Ok(())
+    }
+
+    /// Take the slot if it is open, without blocking. Returns whether the caller passed.
+    pub fn try_pass(&self) -> Result<bool> {
+        match self.pass(Some(Duration::ZERO)) {
+            Ok(()) => Ok(true),
+            Err(WaitObjectError::Timeout) => Ok(false),
+            Err(e) => Err(e)
+        }
+    }
+}
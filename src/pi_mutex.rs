@@ -0,0 +1,255 @@
+//! Mutex which avoids priority inversion with real-time threads.
+//!
+//! On Linux, [`PiMutex`] is a pthread mutex with the `PTHREAD_PRIO_INHERIT` protocol: while a higher priority thread
+//! waits for the lock, its owner runs at that thread's priority. On Windows, it is a native kernel mutex. Windows has
+//! no priority inheritance protocol, and relies on the scheduler boosting starved ready threads instead.
+
+use std::{ cell::UnsafeCell, marker::PhantomData, ops::{ Deref, DerefMut }, time::Duration };
+use crate::Result;
+
+// ------------------------------ DATA TYPES ----------------------------------
+/// Mutual exclusion lock over `T` with priority inheritance.
+///
+/// Unlike [`std::sync::Mutex`], a panic while the lock is held does not poison it.
+///
+/// # Examples
+///
+/// ```rust
+/// # use std::{sync::Arc, thread, time::Duration};
+/// use sync_wait_object::{PiMutex, WaitObjectError};
+///
+/// let buffer = Arc::new(PiMutex::new(Vec::<f32>::new()).unwrap());
+///
+/// let mut samples = buffer.lock().unwrap();
+/// samples.push(0.5);
+///
+/// let other = buffer.clone();
+/// let busy = thread::spawn(move || {
+///     assert!(other.try_lock().unwrap().is_none());
+///     matches!(other.timed_lock(Duration::from_millis(10)), Err(WaitObjectError::Timeout))
+/// }).join().unwrap();
+/// assert!(busy);
+///
+/// drop(samples);
+/// assert_eq!(*buffer.timed_lock(Duration::from_secs(1)).unwrap(), vec![0.5]);
+/// ```
+pub struct PiMutex<T> {
+    raw: imp::RawMutex,
+    value: UnsafeCell<T>
+}
+
+/// Guard of a locked [`PiMutex`], which unlocks on drop. It must be dropped on the thread which locked.
+pub struct PiMutexGuard<'a, T> {
+    mutex: &'a PiMutex<T>,
+    _not_send: PhantomData<*const ()>
+}
+
+// SAFETY: the mutex hands out access to the value to one thread at a time
+unsafe impl<T: Send> Send for PiMutex<T> {}
+unsafe impl<T: Send> Sync for PiMutex<T> {}
+unsafe impl<T: Sync> Sync for PiMutexGuard<'_, T> {}
+
+// ------------------------------ IMPLEMENTATIONS ------------------------------
+impl<T> PiMutex<T> {
+    pub fn new(value: T) -> Result<Self> {
+        Ok(Self { raw: imp::RawMutex::new()?, value: UnsafeCell::new(value) })
+    }
+
+    /// Block until the lock is acquired.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the calling thread already holds the lock.
+    #[inline]
+    pub fn lock(&self) -> Result<PiMutexGuard<'_, T>> {
+        self.raw.lock(None)?;
+        Ok(self.guard())
+    }
+
+    /// Acquire the lock if it is free, without blocking. Returns `None` if another thread holds it.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the calling thread already holds the lock.
+    pub fn try_lock(&self) -> Result<Option<PiMutexGuard<'_, T>>> {
+        Ok(if self.raw.try_lock()? { Some(self.guard()) } else { None })
+    }
+
+    /// Block until the lock is acquired, or fail with [`WaitObjectError::Timeout`](crate::WaitObjectError::Timeout)
+    /// after `timeout`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the calling thread already holds the lock.
+    #[inline]
+    pub fn timed_lock(&self, timeout: Duration) -> Result<PiMutexGuard<'_, T>> {
+        self.raw.lock(Some(timeout))?;
+        Ok(self.guard())
+    }
+
+    #[inline]
+    pub fn get_mut(&mut self) -> &mut T { self.value.get_mut() }
+
+    #[inline]
+    pub fn into_inner(self) -> T { self.value.into_inner() }
+
+    #[inline]
+    fn guard(&self) -> PiMutexGuard<'_, T> {
+        PiMutexGuard { mutex: self, _not_send: PhantomData }
+    }
+}
+
+impl<T> Deref for PiMutexGuard<'_, T> {
+    type Target = T;
+
+    fn deref(&self) -> &Self::Target {
+        unsafe { &*self.mutex.value.get() }
+    }
+}
+
+impl<T> DerefMut for PiMutexGuard<'_, T> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        unsafe { &mut *self.mutex.value.get() }
+    }
+}
+
+impl<T> Drop for PiMutexGuard<'_, T> {
+    fn drop(&mut self) {
+        self.mutex.raw.unlock();
+    }
+}
+
+fn relock() -> ! {
+    panic!("PiMutex is already locked by the current thread")
+}
+
+#[cfg(target_os = "linux")]
+mod imp {
+    use std::{ cell::UnsafeCell, io, mem::MaybeUninit, time::{ Duration, Instant } };
+    use crate::{ Deadline, Result, WaitObjectError };
+
+    /// Error-checking pthread mutex with the priority inheritance protocol. Boxed, as it must not move once initialized.
+    pub(super) struct RawMutex(Box<UnsafeCell<libc::pthread_mutex_t>>);
+
+    fn check(code: libc::c_int) -> Result<()> {
+        if code == 0 { Ok(()) }
+        else { Err(WaitObjectError::OsError(code as isize, io::Error::from_raw_os_error(code).to_string())) }
+    }
+
+    impl RawMutex {
+        pub(super) fn new() -> Result<Self> {
+            let mut attr = MaybeUninit::<libc::pthread_mutexattr_t>::uninit();
+            check(unsafe { libc::pthread_mutexattr_init(attr.as_mut_ptr()) })?;
+            let attr = attr.as_mut_ptr();
+            let mutex = Box::new(UnsafeCell::new(libc::PTHREAD_MUTEX_INITIALIZER));
+            let r = unsafe {
+                check(libc::pthread_mutexattr_setprotocol(attr, libc::PTHREAD_PRIO_INHERIT))
+                    .and_then(|_| check(libc::pthread_mutexattr_settype(attr, libc::PTHREAD_MUTEX_ERRORCHECK)))
+                    .and_then(|_| check(libc::pthread_mutex_init(mutex.get(), attr)))
+            };
+            unsafe { libc::pthread_mutexattr_destroy(attr); }
+            r.map(|_| Self(mutex))
+        }
+
+        pub(super) fn lock(&self, timeout: Option<Duration>) -> Result<()> {
+            let Some(deadline) = Deadline::from_timeout(timeout).instant() else {
+                return self.checked(unsafe { libc::pthread_mutex_lock(self.0.get()) });
+            };
+            // pthread_mutex_timedlock takes an absolute CLOCK_REALTIME time
+            let mut now = libc::timespec { tv_sec: 0, tv_nsec: 0 };
+            unsafe { libc::clock_gettime(libc::CLOCK_REALTIME, &mut now) };
+            let Some(at) = Duration::new(now.tv_sec as u64, now.tv_nsec as u32).checked_add(deadline.saturating_duration_since(Instant::now()))
+                else { return self.lock(None) };
+            let at = libc::timespec { tv_sec: at.as_secs() as libc::time_t, tv_nsec: at.subsec_nanos() as _ };
+            match unsafe { libc::pthread_mutex_timedlock(self.0.get(), &at) } {
+                libc::ETIMEDOUT => Err(WaitObjectError::Timeout),
+                code => self.checked(code)
+            }
+        }
+
+        pub(super) fn try_lock(&self) -> Result<bool> {
+            match unsafe { libc::pthread_mutex_trylock(self.0.get()) } {
+                libc::EBUSY => Ok(false),
+                code => self.checked(code).map(|_| true)
+            }
+        }
+
+        fn checked(&self, code: libc::c_int) -> Result<()> {
+            if code == libc::EDEADLK { super::relock() }
+            check(code)
+        }
+
+        pub(super) fn unlock(&self) {
+            unsafe { libc::pthread_mutex_unlock(self.0.get()); }
+        }
+    }
+
+    impl Drop for RawMutex {
+        fn drop(&mut self) {
+            unsafe { libc::pthread_mutex_destroy(self.0.get()); }
+        }
+    }
+}
+
+#[cfg(windows)]
+mod imp {
+    use std::{ sync::atomic::{ AtomicBool, Ordering }, time::Duration };
+    use windows::Win32::{
+        Foundation::{ WAIT_ABANDONED, WAIT_FAILED, WAIT_OBJECT_0, WAIT_TIMEOUT },
+        System::Threading::{ CreateMutexA, ReleaseMutex, WaitForSingleObject }
+    };
+    use crate::{ Deadline, Result, WaitObjectError, windows::{ WaitEvent, get_last_error } };
+
+    /// Kernel mutex. Kernel mutexes are recursive, so `held` detects a relock by the owner.
+    pub(super) struct RawMutex {
+        handle: WaitEvent,
+        held: AtomicBool
+    }
+
+    impl RawMutex {
+        pub(super) fn new() -> Result<Self> {
+            let handle = unsafe { CreateMutexA(None, false, None) }.map_err(|_| get_last_error())?;
+            Ok(Self { handle: WaitEvent(handle), held: AtomicBool::new(false) })
+        }
+
+        pub(super) fn lock(&self, timeout: Option<Duration>) -> Result<()> {
+            let deadline = Deadline::from_timeout(timeout);
+            loop {
+                match unsafe { WaitForSingleObject(self.handle.0, deadline.win32_millis()) } {
+                    WAIT_OBJECT_0 => return self.acquired(),
+                    // an abandoned mutex is acquired all the same, and `held` is still set by its dead owner
+                    WAIT_ABANDONED => {
+                        self.held.store(false, Ordering::Relaxed);
+                        return self.acquired();
+                    },
+                    // a budget beyond the longest Win32 wait continues with another wait
+                    WAIT_TIMEOUT if !deadline.is_expired() => continue,
+                    WAIT_TIMEOUT => return Err(WaitObjectError::Timeout),
+                    WAIT_FAILED => return Err(get_last_error()),
+                    _ => unreachable!()
+                }
+            }
+        }
+
+        pub(super) fn try_lock(&self) -> Result<bool> {
+            match self.lock(Some(Duration::ZERO)) {
+                Ok(()) => Ok(true),
+                Err(WaitObjectError::Timeout) => Ok(false),
+                Err(e) => Err(e)
+            }
+        }
+
+        fn acquired(&self) -> Result<()> {
+            if self.held.swap(true, Ordering::Acquire) {
+                unsafe { ReleaseMutex(self.handle.0); }
+                super::relock()
+            }
+            Ok(())
+        }
+
+        pub(super) fn unlock(&self) {
+            self.held.store(false, Ordering::Release);
+            unsafe { ReleaseMutex(self.handle.0); }
+        }
+    }
+}
@@ -0,0 +1,88 @@
+use std::{ cell::Cell, sync::RwLock, time::Duration };
+use crate::Deadline;
+
+// ------------------------------ DATA TYPES ----------------------------------
+/// Defaults consulted by all waits of all events, unless overridden by the event (see
+/// [`WaitEventBuilder::default_timeout`](crate::WaitEventBuilder::default_timeout)) or the call.
+///
+/// The policy is process-wide ([`set_global`](Self::set_global)), and can be overridden for the current thread
+/// within a [`scope`](Self::scope).
+///
+/// # Examples
+///
+/// ```rust
+/// # use std::time::Duration;
+/// use sync_wait_object::{WaitEvent, WaitPolicy, WaitObjectError};
+///
+/// let ev = WaitEvent::new_init(0);
+/// let policy = WaitPolicy { default_timeout: Some(Duration::from_millis(10)), ..WaitPolicy::DEFAULT };
+///
+/// let r = WaitPolicy::scope(policy, || ev.wait(None, |v| *v == 1).map(|_| ()));
+/// assert_eq!(r, Err(WaitObjectError::Timeout));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct WaitPolicy {
+    /// Timeout of waits given `None`, when the event has no default timeout of its own
+    pub default_timeout: Option<Duration>,
+
+    /// Number of times the condition is re-checked by spinning before blocking
+    pub spin_count: u32,
+
+    /// Panic when a wait given `None` would end up without any timeout, to find unbounded waits in debug builds.
+    /// [`WaitEvent::wait_forever`](crate::WaitEvent::wait_forever) is not affected.
+    pub panic_on_infinite_wait: bool
+}
+
+static GLOBAL: RwLock<WaitPolicy> = RwLock::new(WaitPolicy::DEFAULT);
+
+thread_local! {
+    static SCOPED: Cell<Option<WaitPolicy>> = const { Cell::new(None) };
+}
+
+/// Restores the previous thread policy when a scope ends, even by panic.
+struct ScopeGuard(Option<WaitPolicy>);
+
+// ------------------------------ IMPLEMENTATIONS ------------------------------
+impl WaitPolicy {
+    /// Policy in effect unless changed: no default timeout, no spinning, no panic.
+    pub const DEFAULT: Self = Self { default_timeout: None, spin_count: 0, panic_on_infinite_wait: false };
+
+    /// Policy in effect on the current thread.
+    pub fn current() -> Self {
+        SCOPED.with(Cell::get).unwrap_or_else(|| *GLOBAL.read().unwrap_or_else(|e| e.into_inner()))
+    }
+
+    /// Replace the process-wide policy.
+    pub fn set_global(policy: Self) {
+        *GLOBAL.write().unwrap_or_else(|e| e.into_inner()) = policy;
+    }
+
+    /// Run `f` with `policy` in effect on the current thread.
+    pub fn scope<R>(policy: Self, f: impl FnOnce() -> R) -> R {
+        let _guard = ScopeGuard(SCOPED.with(|s| s.replace(Some(policy))));
+        f()
+    }
+
+    /// Timeout of a wait given `None`, for an event whose default timeout is `event_timeout`.
+    ///
+    /// # Panics
+    ///
+    /// If the wait would be unbounded while [`panic_on_infinite_wait`](Self::panic_on_infinite_wait) is set.
+    pub(crate) fn unset_timeout(&self, event_timeout: Option<Duration>) -> Option<Duration> {
+        let timeout = event_timeout.or(self.default_timeout);
+        let bounded = timeout.is_some() || Deadline::current() != Deadline::NEVER;
+        assert!(bounded || !self.panic_on_infinite_wait, "infinite wait while WaitPolicy::panic_on_infinite_wait is set");
+        timeout
+    }
+}
+
+impl Default for WaitPolicy {
+    #[inline]
+    fn default() -> Self { Self::DEFAULT }
+}
+
+impl Drop for ScopeGuard {
+    fn drop(&mut self) {
+        SCOPED.with(|s| s.set(self.0));
+    }
+}
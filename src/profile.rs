@@ -0,0 +1,205 @@
+use std::{ collections::VecDeque, fmt, fmt::Write, thread::{ self, ThreadId }, time::{ Duration, Instant } };
+use crate::{ EventBackend, WaitEvent, Result };
+
+/// Number of most recent sets kept in a [`WakeReport`].
+pub const PROFILE_CAPACITY: usize = 1024;
+
+// ------------------------------ DATA TYPES ----------------------------------
+/// Thread which set or waited for an event.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ThreadLabel {
+    pub id: ThreadId,
+    pub name: Option<String>
+}
+
+/// One set of an event, with the waiters it woke up. Times are relative to the start of the profile.
+#[derive(Debug, Clone)]
+pub struct SetRecord {
+    pub at: Duration,
+    pub setter: ThreadLabel,
+    pub woken: Vec<(ThreadLabel, Duration)>
+}
+
+/// Number of times a setter thread woke a waiter thread up.
+#[derive(Debug, Clone)]
+pub struct WakeEdge {
+    pub setter: ThreadLabel,
+    pub woken: ThreadLabel,
+    pub count: u64
+}
+
+/// Who woke whom on an event, from [`WaitEvent::wake_report`]. A wake-up is attributed to the latest set before it.
+/// Printed with `Display` as a text report, or with [`to_json`](WakeReport::to_json).
+#[derive(Debug, Clone)]
+pub struct WakeReport {
+    /// Name of the event, if any
+    pub event: Option<String>,
+
+    /// Most recent sets, oldest first (up to [`PROFILE_CAPACITY`])
+    pub sets: Vec<SetRecord>,
+
+    /// Wake-up counts per setter and waiter thread, over the whole profile
+    pub edges: Vec<WakeEdge>
+}
+
+pub(crate) struct Profiler {
+    start: Instant,
+    sets: VecDeque<SetRecord>,
+    edges: Vec<WakeEdge>
+}
+
+// ------------------------------ FUNCTIONS -------------------------------------
+fn json_string(out: &mut String, s: &str) {
+    out.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            c if (c as u32) < 0x20 => { let _ = write!(out, "\\u{:04x}", c as u32); },
+            c => out.push(c)
+        }
+    }
+    out.push('"');
+}
+
+// ------------------------------ IMPLEMENTATIONS ------------------------------
+impl ThreadLabel {
+    fn current() -> Self {
+        let current = thread::current();
+        Self { id: current.id(), name: current.name().map(str::to_string) }
+    }
+
+    fn write_json(&self, out: &mut String) {
+        out.push_str("{\"id\":");
+        json_string(out, &format!("{:?}", self.id));
+        out.push_str(",\"name\":");
+        match &self.name {
+            Some(name) => json_string(out, name),
+            None => out.push_str("null")
+        }
+        out.push('}');
+    }
+}
+
+impl fmt::Display for ThreadLabel {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.name {
+            Some(name) => write!(f, "{name} ({:?})", self.id),
+            None => write!(f, "{:?}", self.id)
+        }
+    }
+}
+
+impl Profiler {
+    pub(crate) fn new() -> Self {
+        Self { start: Instant::now(), sets: VecDeque::new(), edges: Vec::new() }
+    }
+
+    pub(crate) fn record_set(&mut self) {
+        if self.sets.len() == PROFILE_CAPACITY { self.sets.pop_front(); }
+        self.sets.push_back(SetRecord { at: self.start.elapsed(), setter: ThreadLabel::current(), woken: Vec::new() });
+    }
+
+    pub(crate) fn record_wake(&mut self) {
+        let at = self.start.elapsed();
+        let Some(set) = self.sets.back_mut() else { return };
+        let woken = ThreadLabel::current();
+        match self.edges.iter_mut().find(|e| e.setter.id == set.setter.id && e.woken.id == woken.id) {
+            Some(edge) => edge.count += 1,
+            None => self.edges.push(WakeEdge { setter: set.setter.clone(), woken: woken.clone(), count: 1 })
+        }
+        set.woken.push((woken, at));
+    }
+}
+
+impl WakeReport {
+    /// The report as a JSON object, with durations in microseconds.
+    pub fn to_json(&self) -> String {
+        let mut out = String::from("{\"event\":");
+        match &self.event {
+            Some(name) => json_string(&mut out, name),
+            None => out.push_str("null")
+        }
+        out.push_str(",\"sets\":[");
+        for (i, set) in self.sets.iter().enumerate() {
+            if i > 0 { out.push(',') }
+            let _ = write!(out, "{{\"at_us\":{},\"setter\":", set.at.as_micros());
+            set.setter.write_json(&mut out);
+            out.push_str(",\"woken\":[");
+            for (j, (thread, at)) in set.woken.iter().enumerate() {
+                if j > 0 { out.push(',') }
+                let _ = write!(out, "{{\"at_us\":{},\"thread\":", at.as_micros());
+                thread.write_json(&mut out);
+                out.push('}');
+            }
+            out.push_str("]}");
+        }
+        out.push_str("],\"edges\":[");
+        for (i, edge) in self.edges.iter().enumerate() {
+            if i > 0 { out.push(',') }
+            out.push_str("{\"setter\":");
+            edge.setter.write_json(&mut out);
+            out.push_str(",\"woken\":");
+            edge.woken.write_json(&mut out);
+            let _ = write!(out, ",\"count\":{}}}", edge.count);
+        }
+        out.push_str("]}");
+        out
+    }
+}
+
+impl fmt::Display for WakeReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "wake-up report of {}", self.event.as_deref().unwrap_or("unnamed event"))?;
+        for edge in &self.edges {
+            writeln!(f, "  {} woke {} x{}", edge.setter, edge.woken, edge.count)?;
+        }
+        for set in &self.sets {
+            writeln!(f, "  {:>12?} set by {}", set.at, set.setter)?;
+            for (thread, at) in &set.woken {
+                writeln!(f, "  {at:>12?}   woke {thread}")?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl<T, B: EventBackend<T>> WaitEvent<T, B> {
+    /// Report of which threads set this event, and which waiters each set woke up.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use std::{thread, time::Duration};
+    /// use sync_wait_object::WaitEvent;
+    ///
+    /// let ready = WaitEvent::new_init(false);
+    /// let mut setter = ready.clone();
+    /// thread::Builder::new().name("loader".into()).spawn(move || {
+    ///     thread::sleep(Duration::from_millis(20));
+    ///     setter.set_state(true).unwrap();
+    /// }).unwrap();
+    ///
+    /// drop(ready.wait(Some(Duration::from_secs(1)), |r| *r).unwrap());
+    ///
+    /// let report = ready.wake_report().unwrap();
+    /// assert_eq!(report.edges[0].setter.name.as_deref(), Some("loader"));
+    /// assert_eq!(report.edges[0].woken.id, thread::current().id());
+    /// assert!(report.to_json().contains("\"name\":\"loader\""));
+    /// println!("{report}");
+    /// ```
+    pub fn wake_report(&self) -> Result<WakeReport> {
+        let profiler = self.0.profile.lock()?;
+        Ok(WakeReport {
+            event: self.0.name.as_deref().map(str::to_string),
+            sets: profiler.sets.iter().cloned().collect(),
+            edges: profiler.edges.clone()
+        })
+    }
+
+    /// Clear the wake-up profile of this event.
+    pub fn reset_profile(&self) -> Result<()> {
+        *self.0.profile.lock()? = Profiler::new();
+        Ok(())
+    }
+}
@@ -0,0 +1,136 @@
+use std::{ collections::VecDeque, time::Duration };
+use crate::{ WaitEvent, Result, WaitObjectError };
+
+// ------------------------------ DATA TYPES ----------------------------------
+/// Event which queues every set value into a bounded FIFO, rather than overwriting a single slot. Each
+/// [`wait_next`](Self::wait_next) consumes exactly one value, so bursts are never coalesced.
+///
+/// # Examples
+///
+/// ```rust
+/// # use std::{thread, time::Duration};
+/// use sync_wait_object::QueuedEvent;
+///
+/// let events = QueuedEvent::new(16);
+/// let mut producer = events.clone();
+///
+/// thread::spawn(move || for i in 0..3 { producer.set_state(i).unwrap() });
+///
+/// let timeout = Some(Duration::from_secs(1));
+/// let received: Vec<_> = (0..3).map(|_| events.wait_next(timeout).unwrap()).collect();
+/// assert_eq!(received, vec![0, 1, 2]);
+/// ```
+pub struct QueuedEvent<T> {
+    inner: WaitEvent<VecDeque<T>>,
+    capacity: usize
+}
+
+// ------------------------------ IMPLEMENTATIONS ------------------------------
+impl<T> QueuedEvent<T> {
+    /// Create an empty event, which holds at most `capacity` values.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `capacity` is zero.
+    pub fn new(capacity: usize) -> Self {
+        assert!(capacity > 0, "QueuedEvent capacity must be greater than zero");
+        Self { inner: WaitEvent::new_init(VecDeque::with_capacity(capacity)), capacity }
+    }
+
+    #[inline]
+    pub fn capacity(&self) -> usize { self.capacity }
+
+    /// Number of queued values.
+    #[inline]
+    pub fn len(&self) -> Result<usize> { self.inner.value().map(|q| q.len()) }
+
+    #[inline]
+    pub fn is_empty(&self) -> Result<bool> { self.len().map(|n| n == 0) }
+
+    /// Enqueue `value` and wake the waiters. If the queue is full, wait for room up to the default timeout.
+    pub fn set_state(&mut self, value: T) -> Result<()> {
+        let capacity = self.capacity;
+        let mut queue = self.inner.wait_for_update(self.inner.unset_timeout(), |q| q.len() < capacity)?;
+        queue.push_back(value);
+        self.inner.0.notify(&queue);
+        Ok(())
+    }
+
+    /// Enqueue `value` without waiting. Returns the value back if the queue is full.
+    pub fn try_set_state(&mut self, value: T) -> Result<std::result::Result<(), T>> {
+        let mut queue = self.inner.lock_for_update()?;
+        if queue.len() >= self.capacity { return Ok(Err(value)) }
+        queue.push_back(value);
+        self.inner.0.notify(&queue);
+        Ok(Ok(()))
+    }
+
+    /// Wait until a value is queued, then take the oldest one out.
+    ///
+    /// # Arguments
+    ///
+    /// * `timeout` - Maximum wait time
+    pub fn wait_next(&self, timeout: Option<Duration>) -> Result<T> {
+        let mut queue = self.inner.wait_for_update(timeout.or_else(|| self.inner.unset_timeout()), |q| !q.is_empty())?;
+        let value = queue.pop_front().unwrap();
+        // wake producers waiting for room
+        self.inner.0.notify(&queue);
+        Ok(value)
+    }
+
+    /// Wait until at least `min` values are queued, or timed-out from `timeout`, then take up to `max` of the oldest
+    /// values out. A timeout is not an error: the values queued by then are returned, possibly none. Batching amortizes
+    /// the wake-ups of high-throughput consumers.
+    ///
+    /// # Arguments
+    ///
+    /// * `min` - Number of values which ends the wait
+    /// * `max` - Maximum number of values taken
+    /// * `timeout` - Maximum wait time
+    ///
+    /// # Panics
+    ///
+    /// Panics if `min` is greater than `max` or than the capacity.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use std::time::Duration;
+    /// use sync_wait_object::QueuedEvent;
+    ///
+    /// let events = QueuedEvent::new(16);
+    /// let mut producer = events.clone();
+    /// for i in 0..5 { producer.set_state(i).unwrap() }
+    ///
+    /// let timeout = Some(Duration::from_millis(20));
+    /// assert_eq!(events.wait_drain(2, 3, timeout).unwrap(), vec![0, 1, 2]);
+    /// assert_eq!(events.wait_drain(3, 3, timeout).unwrap(), vec![3, 4]);
+    /// ```
+    pub fn wait_drain(&self, min: usize, max: usize, timeout: Option<Duration>) -> Result<Vec<T>> {
+        assert!(min <= max && min <= self.capacity, "wait_drain min must not exceed max nor the capacity");
+        let mut queue = match self.inner.wait_for_update(timeout.or_else(|| self.inner.unset_timeout()), |q| q.len() >= min) {
+            Ok(queue) => queue,
+            Err(WaitObjectError::Timeout) => self.inner.lock_for_update()?,
+            Err(e) => return Err(e)
+        };
+        let n = queue.len().min(max);
+        let batch: Vec<T> = queue.drain(..n).collect();
+        // wake producers waiting for room
+        if n > 0 { self.inner.0.notify(&queue) }
+        Ok(batch)
+    }
+
+    /// Take the oldest queued value out, if any, without waiting.
+    pub fn try_next(&self) -> Result<Option<T>> {
+        let mut queue = self.inner.lock_for_update()?;
+        let value = queue.pop_front();
+        if value.is_some() { self.inner.0.notify(&queue) }
+        Ok(value)
+    }
+}
+
+impl<T> Clone for QueuedEvent<T> {
+    fn clone(&self) -> Self {
+        Self { inner: self.inner.clone(), capacity: self.capacity }
+    }
+}
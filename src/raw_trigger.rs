@@ -0,0 +1,213 @@
+//! Async-signal-safe trigger of boolean events, for Unix signal handlers and Windows console control handlers.
+//!
+//! On Unix, the trigger wakes its dispatcher thread through a socket. On Windows, it does through a Win32 event
+//! signaled with `SetEvent`.
+//!
+//! # Processes
+//!
+//! On Unix, the trigger's socket is close-on-exec by default, so spawned programs do not inherit it. Use
+//! [`set_inheritable`](RawTrigger::set_inheritable) to change that.
+//!
+//! A forked child gets a copy of the trigger, but not its dispatcher thread: triggering the copy has no effect until
+//! [`rearm`](RawTrigger::rearm) gives it a dispatcher of its own in the child.
+
+#[cfg(unix)]
+use std::{
+    io::{ ErrorKind, Read, Write },
+    os::{ fd::AsRawFd, unix::net::UnixStream }
+};
+use std::{ sync::{ Arc, atomic::{ AtomicBool, Ordering } }, thread };
+#[cfg(windows)]
+use windows::Win32::System::Threading::SetEvent;
+#[cfg(windows)]
+use crate::{ SignalWaitable, windows::{ AutoResetEvent as Win32Event, HandleWrapper } };
+#[cfg(unix)]
+use crate::WaitObjectError;
+use crate::{ WaitEvent, ManualResetEvent, AutoResetEvent, Result };
+
+// ------------------------------ DATA TYPES ----------------------------------
+/// Handle which sets its event from a Unix signal handler or a Windows console control handler.
+/// [`trigger`](RawTrigger::trigger) takes no lock and does no allocation: it raises an atomic flag and wakes a
+/// dispatcher thread (by writing one byte to a socket, or by `SetEvent`), which performs the actual set later. The dispatcher exits when the last clone of the handle drops.
+///
+/// # Examples
+///
+/// ```rust
+/// # use std::time::Duration;
+/// use sync_wait_object::{ManualResetEvent, SignalWaitable};
+///
+/// let shutdown = ManualResetEvent::new();
+/// let trigger = shutdown.raw_trigger().unwrap();
+///
+/// // e.g. from a SIGTERM handler
+/// trigger.trigger();
+///
+/// assert!(shutdown.wait(Duration::from_secs(1)).is_ok());
+/// ```
+#[derive(Clone)]
+pub struct RawTrigger {
+    /// Event to re-arm a dispatcher for
+    #[cfg(unix)]
+    event: WaitEvent<bool>,
+    pending: Arc<AtomicBool>,
+    #[cfg(unix)]
+    wake: Arc<UnixStream>,
+    #[cfg(windows)]
+    wake: Arc<Wake>
+}
+
+/// Win32 event waking the dispatcher, which is told to exit when the last clone of the trigger drops.
+#[cfg(windows)]
+struct Wake {
+    event: Arc<Win32Event>,
+    closed: Arc<AtomicBool>
+}
+
+// ------------------------------ IMPLEMENTATIONS ------------------------------
+impl RawTrigger {
+    #[cfg(unix)]
+    fn new(event: WaitEvent<bool>) -> Result<Self> {
+        let (wake, mut receiver) = UnixStream::pair().map_err(to_error)?;
+        wake.set_nonblocking(true).map_err(to_error)?;
+        let pending = Arc::new(AtomicBool::new(false));
+        let dispatch_pending = pending.clone();
+        let mut dispatch_event = event.clone();
+        thread::spawn(move || {
+            let mut buf = [0u8; 16];
+            loop {
+                match receiver.read(&mut buf) {
+                    Ok(0) => break,
+                    Ok(_) => if dispatch_pending.swap(false, Ordering::AcqRel) && dispatch_event.set_state(true).is_err() { break },
+                    Err(e) if e.kind() == ErrorKind::Interrupted => (),
+                    Err(_) => break
+                }
+            }
+        });
+        Ok(Self { event, pending, wake: Arc::new(wake) })
+    }
+
+    #[cfg(windows)]
+    fn new(event: WaitEvent<bool>) -> Result<Self> {
+        let wake = Wake { event: Arc::new(Win32Event::new()), closed: Arc::new(AtomicBool::new(false)) };
+        let pending = Arc::new(AtomicBool::new(false));
+        let dispatch_pending = pending.clone();
+        let mut dispatch_event = event;
+        let (dispatch_wake, dispatch_closed) = (wake.event.clone(), wake.closed.clone());
+        thread::spawn(move || {
+            while dispatch_wake.wait_until_set().is_ok() && !dispatch_closed.load(Ordering::Acquire) {
+                if dispatch_pending.swap(false, Ordering::AcqRel) && dispatch_event.set_state(true).is_err() { break }
+            }
+        });
+        Ok(Self { pending, wake: Arc::new(wake) })
+    }
+
+    /// Request the event to be set. Async-signal-safe.
+    #[inline]
+    pub fn trigger(&self) {
+        if !self.pending.swap(true, Ordering::AcqRel) {
+            #[cfg(unix)]
+            let _ = (&*self.wake).write(&[1]);
+            // SAFETY: the handle is owned by the dispatcher's event, which lives as long as `wake`
+            #[cfg(windows)]
+            unsafe { SetEvent(self.wake.event.handle()); }
+        }
+    }
+}
+
+#[cfg(unix)]
+impl RawTrigger {
+    /// Whether the trigger's socket is inherited by programs spawned with `exec`.
+    pub fn is_inheritable(&self) -> Result<bool> {
+        let flags = unsafe { libc::fcntl(self.wake.as_raw_fd(), libc::F_GETFD) };
+        if flags < 0 { return Err(to_error(std::io::Error::last_os_error())) }
+        Ok(flags & libc::FD_CLOEXEC == 0)
+    }
+
+    /// Set whether the trigger's socket is inherited by programs spawned with `exec`, by clearing or setting its
+    /// `FD_CLOEXEC` flag. This applies to all clones of the trigger.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use sync_wait_object::ManualResetEvent;
+    ///
+    /// let trigger = ManualResetEvent::new().raw_trigger().unwrap();
+    /// assert!(!trigger.is_inheritable().unwrap());
+    ///
+    /// trigger.set_inheritable(true).unwrap();
+    /// assert!(trigger.is_inheritable().unwrap());
+    /// ```
+    pub fn set_inheritable(&self, inheritable: bool) -> Result<()> {
+        let fd = self.wake.as_raw_fd();
+        let flags = unsafe { libc::fcntl(fd, libc::F_GETFD) };
+        if flags < 0 { return Err(to_error(std::io::Error::last_os_error())) }
+        let flags = if inheritable { flags & !libc::FD_CLOEXEC } else { flags | libc::FD_CLOEXEC };
+        if unsafe { libc::fcntl(fd, libc::F_SETFD, flags) } < 0 { return Err(to_error(std::io::Error::last_os_error())) }
+        Ok(())
+    }
+
+    /// Give this trigger a new socket and dispatcher thread, for use in a forked child. Other clones of the trigger in
+    /// the child must be re-armed (or re-cloned from this one) as well. Spawning a thread is not async-signal-safe, so
+    /// call this from a child which is known to be safe to continue (e.g. the parent was single-threaded), and not
+    /// between `fork` and `exec`.
+    ///
+    /// # Examples
+    ///
+    /// Not run as a test: the parent below forks while its own dispatcher thread runs, so the child is only safe to
+    /// continue if that thread held no lock at the time, which the precondition above does not allow to assume.
+    ///
+    /// ```rust,no_run
+    /// # use std::time::Duration;
+    /// use sync_wait_object::{ManualResetEvent, SignalWaitable};
+    ///
+    /// let ev = ManualResetEvent::new();
+    /// let mut trigger = ev.raw_trigger().unwrap();
+    ///
+    /// match unsafe { libc::fork() } {
+    ///     0 => {
+    ///         trigger.rearm().unwrap();
+    ///         trigger.trigger();
+    ///         let code = if ev.wait(Duration::from_secs(1)).is_ok() { 0 } else { 1 };
+    ///         unsafe { libc::_exit(code) }
+    ///     },
+    ///     child => {
+    ///         let mut status = 0;
+    ///         unsafe { libc::waitpid(child, &mut status, 0) };
+    ///         assert!(libc::WIFEXITED(status) && libc::WEXITSTATUS(status) == 0);
+    ///     }
+    /// }
+    /// assert!(!ev.is_set().unwrap());
+    /// ```
+    pub fn rearm(&mut self) -> Result<()> {
+        *self = Self::new(self.event.clone())?;
+        Ok(())
+    }
+}
+
+#[cfg(unix)]
+fn to_error(e: std::io::Error) -> WaitObjectError {
+    WaitObjectError::OsError(e.raw_os_error().unwrap_or_default() as isize, e.to_string())
+}
+
+impl ManualResetEvent {
+    /// Create an async-signal-safe [`RawTrigger`] for this event.
+    pub fn raw_trigger(&self) -> Result<RawTrigger> {
+        RawTrigger::new(self.0.clone())
+    }
+}
+
+impl AutoResetEvent {
+    /// Create an async-signal-safe [`RawTrigger`] for this event.
+    pub fn raw_trigger(&self) -> Result<RawTrigger> {
+        RawTrigger::new(self.0.clone())
+    }
+}
+
+#[cfg(windows)]
+impl Drop for Wake {
+    fn drop(&mut self) {
+        self.closed.store(true, Ordering::Release);
+        // SAFETY: the handle is owned by `self.event`
+        unsafe { SetEvent(self.event.handle()); }
+    }
+}
@@ -0,0 +1,63 @@
+use std::{ sync::{ Arc, Mutex }, time::Duration };
+use crate::{ ManualResetEvent, WaitObjectError, Result };
+
+// ------------------------------ DATA TYPES ----------------------------------
+/// Startup coordinator: subsystems register a named readiness event, and the bootstrap code waits for all of them,
+/// learning which ones are still pending on timeout.
+///
+/// # Examples
+///
+/// ```rust
+/// # use std::{thread, time::Duration};
+/// use sync_wait_object::{Readiness, SignalWaitable};
+///
+/// let readiness = Readiness::new();
+/// let mut db = readiness.register("database");
+/// let _cache = readiness.register("cache");
+///
+/// thread::spawn(move || db.set().unwrap());
+///
+/// let pending = readiness.wait_all_ready(Some(Duration::from_millis(50))).unwrap();
+/// assert_eq!(pending, Err(vec!["cache".to_string()]));
+/// ```
+#[derive(Clone, Default)]
+pub struct Readiness(Arc<Mutex<Vec<(String, ManualResetEvent)>>>);
+
+// ------------------------------ IMPLEMENTATIONS ------------------------------
+impl Readiness {
+    #[inline]
+    pub fn new() -> Self { Self::default() }
+
+    /// Register a component, and get the event it sets once ready.
+    pub fn register(&self, name: impl Into<String>) -> ManualResetEvent {
+        let event = ManualResetEvent::new();
+        self.0.lock().unwrap_or_else(|e| e.into_inner()).push((name.into(), event.clone()));
+        event
+    }
+
+    /// Names of the components which are not ready yet, in registration order.
+    pub fn pending(&self) -> Result<Vec<String>> {
+        let components = self.components();
+        let mut pending = Vec::new();
+        for (name, event) in components {
+            if !event.is_set()? { pending.push(name) }
+        }
+        Ok(pending)
+    }
+
+    /// Wait until all components registered so far are ready, or timed-out from `timeout`. On timeout, the inner
+    /// result holds the names of the pending components.
+    pub fn wait_all_ready(&self, timeout: Option<Duration>) -> Result<std::result::Result<(), Vec<String>>> {
+        let components = self.components();
+        let events: Vec<_> = components.iter().map(|(_, e)| e).collect();
+        match ManualResetEvent::wait_quorum(&events, events.len(), timeout) {
+            Ok(_) => Ok(Ok(())),
+            Err(WaitObjectError::Timeout) => self.pending().map(Err),
+            Err(e) => Err(e)
+        }
+    }
+
+    fn components(&self) -> Vec<(String, ManualResetEvent)> {
+        self.0.lock().unwrap_or_else(|e| e.into_inner()).clone()
+    }
+}
@@ -0,0 +1,47 @@
+use std::{ any::Any, collections::HashMap, sync::Mutex };
+use crate::{ WaitEvent, ManualResetEvent, WaitObjectError, Result };
+
+/// Process-wide events by name.
+static REGISTRY: Mutex<Option<HashMap<String, Box<dyn Any + Send>>>> = Mutex::new(None);
+
+impl<T: Send + 'static> WaitEvent<T> {
+    /// Get the event registered as `name` in this process, or register a new one with the state from `init`.
+    ///
+    /// Fails with [`WaitObjectError::TypeMismatch`] if `name` is registered with another state type.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use sync_wait_object::{WaitEvent, WaitObjectError};
+    ///
+    /// let mut ready = WaitEvent::get_or_create("db-ready", || 0u8).unwrap();
+    /// ready.set_state(1).unwrap();
+    ///
+    /// let same = WaitEvent::<u8>::get_or_create("db-ready", || 0).unwrap();
+    /// assert_eq!(*same.value().unwrap(), 1);
+    ///
+    /// assert_eq!(WaitEvent::get_or_create("db-ready", || "oops").err(), Some(WaitObjectError::TypeMismatch));
+    /// ```
+    pub fn get_or_create(name: &str, init: impl FnOnce() -> T) -> Result<Self> {
+        let mut registry = REGISTRY.lock()?;
+        let entry = registry.get_or_insert_with(HashMap::new)
+            .entry(name.to_string())
+            .or_insert_with(|| Box::new(WaitEvent::new_init(init())));
+        entry.downcast_ref::<WaitEvent<T>>().cloned().ok_or(WaitObjectError::TypeMismatch)
+    }
+
+    /// Remove `name` from the registry. Existing handles stay valid.
+    pub fn unregister(name: &str) -> Result<bool> {
+        let mut registry = REGISTRY.lock()?;
+        Ok(registry.as_mut().and_then(|r| r.remove(name)).is_some())
+    }
+}
+
+impl ManualResetEvent {
+    /// Get the event registered as `name` in this process, or register a new unset one. See
+    /// [`WaitEvent::get_or_create`].
+    #[inline]
+    pub fn named(name: &str) -> Result<Self> {
+        WaitEvent::get_or_create(name, || false).map(Self)
+    }
+}
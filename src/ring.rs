@@ -0,0 +1,200 @@
+use std::{ cell::UnsafeCell, mem::MaybeUninit, sync::{ Arc, atomic::{ AtomicUsize, Ordering } }, time::Duration };
+use crate::{ Deadline, WaitEvent, Result };
+
+// ------------------------------ DATA TYPES ----------------------------------
+/// Producing half of a single-producer single-consumer ring buffer from [`ring_buffer`].
+pub struct RingProducer<T>(Arc<Ring<T>>);
+
+/// Consuming half of a single-producer single-consumer ring buffer from [`ring_buffer`].
+pub struct RingConsumer<T>(Arc<Ring<T>>);
+
+struct Ring<T> {
+    /// Power-of-two number of slots, so that slot indices stay consistent when the counters wrap around
+    slots: Box<[UnsafeCell<MaybeUninit<T>>]>,
+
+    /// Maximum number of records, at most the number of slots
+    capacity: usize,
+
+    /// Next slot to read, only advanced by the consumer
+    head: AtomicUsize,
+
+    /// Next slot to write, only advanced by the producer
+    tail: AtomicUsize,
+
+    data_available: WaitEvent<bool>,
+    space_available: WaitEvent<bool>
+}
+
+// ------------------------------ FUNCTIONS -------------------------------------
+/// Create a bounded single-producer single-consumer ring buffer of `capacity` records. Records are moved in and out
+/// without locking, the crate's events only signal "data available" and "space available" to a blocked side.
+///
+/// The buffer and its events live in the memory of the current process, so both halves must be used within it. There
+/// is no shared-memory variant: across processes, records must be exchanged by other means, with
+/// [`NamedEvent`](crate::ipc::NamedEvent)s for the signalling.
+///
+/// # Panics
+///
+/// Panics if `capacity` is zero.
+///
+/// # Examples
+///
+/// ```rust
+/// # use std::{thread, time::Duration};
+/// use sync_wait_object::ring_buffer;
+///
+/// let (mut tx, mut rx) = ring_buffer(2);
+/// let timeout = Some(Duration::from_secs(1));
+///
+/// let producer = thread::spawn(move || for i in 0..10 { tx.push(i, timeout).unwrap() });
+///
+/// let received: Vec<_> = (0..10).map(|_| rx.pop(timeout).unwrap()).collect();
+/// assert_eq!(received, (0..10).collect::<Vec<_>>());
+/// producer.join().unwrap();
+/// ```
+pub fn ring_buffer<T>(capacity: usize) -> (RingProducer<T>, RingConsumer<T>) {
+    assert!(capacity > 0, "ring buffer capacity must be greater than zero");
+    let ring = Arc::new(Ring {
+        slots: (0..capacity.next_power_of_two()).map(|_| UnsafeCell::new(MaybeUninit::uninit())).collect(),
+        capacity,
+        head: AtomicUsize::new(0),
+        tail: AtomicUsize::new(0),
+        data_available: WaitEvent::new_init(false),
+        space_available: WaitEvent::new_init(false)
+    });
+    (RingProducer(ring.clone()), RingConsumer(ring))
+}
+
+/// Retry `attempt` until it succeeds, waiting on `signal` in between. The signal is reset before re-checking, so a
+/// signal raised after a failed attempt is never lost.
+fn blocking<R>(signal: &WaitEvent<bool>, timeout: Option<Duration>, mut attempt: impl FnMut() -> Option<R>) -> Result<R> {
+    let deadline = timeout.map_or(Deadline::NEVER, Deadline::after);
+    loop {
+        if let Some(r) = attempt() { return Ok(r) }
+        signal.modify_quiet(|v| *v = false)?;
+        if let Some(r) = attempt() { return Ok(r) }
+        drop(signal.wait(deadline.timeout()?, |v| *v)?);
+    }
+}
+
+// ------------------------------ IMPLEMENTATIONS ------------------------------
+// SAFETY: each slot is accessed by one side at a time, as handed over through `head` and `tail`
+unsafe impl<T: Send> Send for Ring<T> {}
+unsafe impl<T: Send> Sync for Ring<T> {}
+
+impl<T> Ring<T> {
+    #[inline]
+    fn capacity(&self) -> usize { self.capacity }
+
+    /// Slot of the record at position `counter` of `head` or `tail`.
+    #[inline]
+    fn slot(&self, counter: usize) -> &UnsafeCell<MaybeUninit<T>> {
+        &self.slots[counter & (self.slots.len() - 1)]
+    }
+
+    #[inline]
+    fn len(&self) -> usize {
+        self.tail.load(Ordering::Acquire).wrapping_sub(self.head.load(Ordering::Acquire))
+    }
+}
+
+impl<T> Drop for Ring<T> {
+    fn drop(&mut self) {
+        let (head, tail) = (*self.head.get_mut(), *self.tail.get_mut());
+        for i in 0..tail.wrapping_sub(head) {
+            let slot = head.wrapping_add(i) & (self.slots.len() - 1);
+            // SAFETY: slots between head and tail are initialized, and nobody else can access them anymore
+            unsafe { self.slots[slot].get_mut().assume_init_drop() }
+        }
+    }
+}
+
+impl<T> RingProducer<T> {
+    #[inline]
+    pub fn capacity(&self) -> usize { self.0.capacity() }
+
+    /// Number of records currently in the buffer.
+    #[inline]
+    pub fn len(&self) -> usize { self.0.len() }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool { self.len() == 0 }
+
+    /// Push `value` without waiting. Returns the value back if the buffer is full.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use sync_wait_object::ring_buffer;
+    ///
+    /// let (mut tx, mut rx) = ring_buffer(3);
+    /// for round in 0..5 {
+    ///     for i in 0..3 { tx.try_push(round * 3 + i).unwrap().unwrap() }
+    ///     assert_eq!(tx.try_push(-1).unwrap(), Err(-1));
+    ///     for i in 0..3 { assert_eq!(rx.try_pop().unwrap(), Some(round * 3 + i)) }
+    /// }
+    /// ```
+    pub fn try_push(&mut self, value: T) -> Result<std::result::Result<(), T>> {
+        let ring = &*self.0;
+        let tail = ring.tail.load(Ordering::Relaxed);
+        if tail.wrapping_sub(ring.head.load(Ordering::Acquire)) == ring.capacity() { return Ok(Err(value)) }
+
+        // SAFETY: the slot is outside of the consumer's range until `tail` is published
+        unsafe { (*ring.slot(tail).get()).write(value); }
+        ring.tail.store(tail.wrapping_add(1), Ordering::Release);
+        ring.data_available.modify(|v| *v = true)?;
+        Ok(Ok(()))
+    }
+
+    /// Push `value`, waiting for space up to `timeout`.
+    ///
+    /// # Arguments
+    ///
+    /// * `value` - Record to push
+    /// * `timeout` - Maximum wait time for space
+    pub fn push(&mut self, value: T, timeout: Option<Duration>) -> Result<()> {
+        let ring = self.0.clone();
+        let mut value = Some(value);
+        blocking(&ring.space_available, timeout, || match self.try_push(value.take().unwrap()) {
+            Ok(Ok(())) => Some(Ok(())),
+            Ok(Err(v)) => { value = Some(v); None },
+            Err(e) => Some(Err(e))
+        })?
+    }
+}
+
+impl<T> RingConsumer<T> {
+    #[inline]
+    pub fn capacity(&self) -> usize { self.0.capacity() }
+
+    /// Number of records currently in the buffer.
+    #[inline]
+    pub fn len(&self) -> usize { self.0.len() }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool { self.len() == 0 }
+
+    /// Pop the oldest record, if any, without waiting.
+    pub fn try_pop(&mut self) -> Result<Option<T>> {
+        let ring = &*self.0;
+        let head = ring.head.load(Ordering::Relaxed);
+        if head == ring.tail.load(Ordering::Acquire) { return Ok(None) }
+
+        // SAFETY: the slot was initialized by the producer before publishing `tail`, and is not reused until `head`
+        // is published
+        let value = unsafe { (*ring.slot(head).get()).assume_init_read() };
+        ring.head.store(head.wrapping_add(1), Ordering::Release);
+        ring.space_available.modify(|v| *v = true)?;
+        Ok(Some(value))
+    }
+
+    /// Pop the oldest record, waiting for one up to `timeout`.
+    ///
+    /// # Arguments
+    ///
+    /// * `timeout` - Maximum wait time for a record
+    pub fn pop(&mut self, timeout: Option<Duration>) -> Result<T> {
+        let ring = self.0.clone();
+        blocking(&ring.data_available, timeout, || self.try_pop().transpose())?
+    }
+}
@@ -0,0 +1,135 @@
+use std::{ thread::{ self, ScopedJoinHandle }, time::Duration };
+use crate::{ ManualResetEvent, SignalWaitable, StaticEvent, WaitEvent, Result };
+
+// ------------------------------ DATA TYPES ----------------------------------
+/// Scope from [`scope`], which tracks the threads it spawns.
+pub struct Scope<'scope, 'env: 'scope> {
+    inner: &'scope thread::Scope<'scope, 'env>,
+    state: &'scope ScopeState
+}
+
+/// Thread spawned by [`Scope::spawn`], with its completion event.
+pub struct ScopedTask<'scope, T> {
+    handle: ScopedJoinHandle<'scope, T>,
+    done: ManualResetEvent
+}
+
+/// State shared by the threads of a scope, borrowed from the stack frame of [`scope`].
+struct ScopeState {
+    /// Number of spawned threads still running
+    running: WaitEvent<usize>,
+    failed: StaticEvent
+}
+
+/// Marks a scoped thread as finished when dropped, including on panic.
+struct TaskExit<'scope> {
+    state: &'scope ScopeState,
+    done: ManualResetEvent
+}
+
+// ------------------------------ FUNCTIONS -------------------------------------
+/// Same as `std::thread::scope`, but every thread spawned through the [`Scope`] gets a completion event, panics are
+/// signaled on a shared error event, and all threads can be waited for with a timeout before the scope joins them. The
+/// shared state is borrowed from this call, not reference counted.
+///
+/// # Examples
+///
+/// ```rust
+/// # use std::time::Duration;
+/// use sync_wait_object::SignalWaitable;
+///
+/// let mut parts = [0u32; 4];
+/// sync_wait_object::scope(|s| {
+///     for (i, part) in parts.iter_mut().enumerate() {
+///         s.spawn(move || *part = i as u32 * 10);
+///     }
+///     s.wait_all(Some(Duration::from_secs(1))).unwrap();
+///     assert!(s.error_event().wait(Duration::ZERO).is_err());
+/// });
+/// assert_eq!(parts, [0, 10, 20, 30]);
+/// ```
+pub fn scope<'env, F, R>(f: F) -> R
+where F: for<'scope> FnOnce(&Scope<'scope, 'env>) -> R
+{
+    let state = ScopeState { running: WaitEvent::new_init(0), failed: StaticEvent::new() };
+    thread::scope(|s| {
+        // SAFETY: `state` outlives the `thread::scope` call, which joins every thread spawned in `'scope` before
+        // returning, even on panic
+        let state = unsafe { &*(&state as *const ScopeState) };
+        f(&Scope { inner: s, state })
+    })
+}
+
+// ------------------------------ IMPLEMENTATIONS ------------------------------
+impl<'scope, 'env> Scope<'scope, 'env> {
+    /// Spawn a scoped thread, tracked by this scope.
+    pub fn spawn<F, T>(&self, f: F) -> ScopedTask<'scope, T>
+    where F: FnOnce() -> T + Send + 'scope,
+          T: Send + 'scope
+    {
+        self.state.running.modify_quiet(|n| *n += 1).expect("the running count cannot be poisoned");
+        let done = ManualResetEvent::new();
+        let exit = TaskExit { state: self.state, done: done.clone() };
+        let handle = self.inner.spawn(move || {
+            let _exit = exit;
+            f()
+        });
+        ScopedTask { handle, done }
+    }
+
+    /// Wait until all threads spawned so far are finished, or timed-out from `timeout`. Like any wait on an event, a
+    /// [`Deadline`](crate::Deadline) scope, a [`CancellationToken`](crate::CancellationToken) scope and the
+    /// [`WaitPolicy`](crate::WaitPolicy) apply.
+    ///
+    /// # Arguments
+    ///
+    /// * `timeout` - Maximum wait time, `None` for the default timeout (see [`WaitPolicy`](crate::WaitPolicy))
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use std::{thread, time::Duration};
+    /// use sync_wait_object::{Deadline, WaitObjectError};
+    ///
+    /// sync_wait_object::scope(|s| {
+    ///     s.spawn(|| thread::sleep(Duration::from_millis(100)));
+    ///     let r = Deadline::after(Duration::from_millis(10)).scope(|| s.wait_all(None));
+    ///     assert_eq!(r, Err(WaitObjectError::Timeout));
+    ///     assert_eq!(s.running().unwrap(), 1);
+    /// });
+    /// ```
+    pub fn wait_all(&self, timeout: Option<Duration>) -> Result<()> {
+        self.state.running.wait(timeout, |n| *n == 0).map(drop)
+    }
+
+    /// Number of spawned threads still running.
+    #[inline]
+    pub fn running(&self) -> Result<usize> { self.state.running.value().map(|n| *n) }
+
+    /// Event set once any thread of the scope panics. Threads may also set it themselves to report a failure.
+    #[inline]
+    pub fn error_event(&self) -> &StaticEvent { &self.state.failed }
+}
+
+impl<'scope, T> ScopedTask<'scope, T> {
+    /// Event set when the thread finishes, by returning or panicking.
+    #[inline]
+    pub fn completion(&self) -> &ManualResetEvent { &self.done }
+
+    #[inline]
+    pub fn is_finished(&self) -> bool { self.handle.is_finished() }
+
+    /// Wait for the thread to finish and get its result, like `ScopedJoinHandle::join`.
+    #[inline]
+    pub fn join(self) -> thread::Result<T> { self.handle.join() }
+}
+
+impl Drop for TaskExit<'_> {
+    fn drop(&mut self) {
+        if thread::panicking() {
+            let _ = self.state.failed.set();
+        }
+        let _ = self.done.set();
+        let _ = self.state.running.modify(|n| *n -= 1);
+    }
+}
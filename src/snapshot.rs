@@ -0,0 +1,65 @@
+use std::sync::MutexGuard;
+use crate::{ WaitEvent, Result };
+
+// ------------------------------ DATA TYPES ----------------------------------
+/// Tuple of event references which can be read consistently by [`snapshot_all`].
+pub trait Snapshot {
+    type Output;
+
+    fn snapshot(self) -> Result<Self::Output>;
+}
+
+// ------------------------------ FUNCTIONS -------------------------------------
+/// Read the values of all events of a tuple `(&WaitEvent<A>, &WaitEvent<B>, ...)` (up to 6) at once. All events are
+/// locked together, in a canonical order to avoid deadlocks, so the values are mutually consistent.
+///
+/// # Panics
+///
+/// Panics if the same event is passed twice.
+///
+/// # Examples
+///
+/// ```rust
+/// use sync_wait_object::{WaitEvent, snapshot_all};
+///
+/// let total = WaitEvent::new_init(10u32);
+/// let status = WaitEvent::new_init("running");
+///
+/// let (t, s) = snapshot_all((&total, &status)).unwrap();
+/// assert_eq!((t, s), (10, "running"));
+/// ```
+#[inline]
+pub fn snapshot_all<S: Snapshot>(events: S) -> Result<S::Output> {
+    events.snapshot()
+}
+
+// ------------------------------ IMPLEMENTATIONS ------------------------------
+macro_rules! impl_snapshot {
+    ($($t:ident $i:tt),+) => {
+        impl<'a, $($t: Clone),+> Snapshot for ($(&'a WaitEvent<$t>,)+) {
+            type Output = ($($t,)+);
+
+            fn snapshot(self) -> Result<Self::Output> {
+                let mut order = [$((self.$i.id(), $i)),+];
+                order.sort_unstable();
+                assert!(order.windows(2).all(|w| w[0].0 != w[1].0), "the same event is passed twice to snapshot_all");
+
+                let mut guards: ($(Option<MutexGuard<'a, $t>>,)+) = Default::default();
+                for (_, index) in order {
+                    match index {
+                        $($i => guards.$i = Some(self.$i.lock_state()?),)+
+                        _ => unreachable!()
+                    }
+                }
+                Ok(($((*guards.$i.as_ref().unwrap()).clone(),)+))
+            }
+        }
+    };
+}
+
+impl_snapshot!(A 0);
+impl_snapshot!(A 0, B 1);
+impl_snapshot!(A 0, B 1, C 2);
+impl_snapshot!(A 0, B 1, C 2, D 3);
+impl_snapshot!(A 0, B 1, C 2, D 3, E 4);
+impl_snapshot!(A 0, B 1, C 2, D 3, E 4, F 5);
@@ -0,0 +1,149 @@
+use std::{ ops::{ Deref, DerefMut }, sync::atomic::Ordering, time::{ Duration, Instant } };
+use crate::{ EventBackend, StdBackend, WaitEvent, Result };
+
+// ------------------------------ DATA TYPES ----------------------------------
+/// Read side of an event from [`WaitEvent::split`]. Waits fail with
+/// [`WaitObjectError::Disconnected`](crate::WaitObjectError::Disconnected) once all [`EventSetter`]s are dropped,
+/// unless the checker already passes. Only reads and waits are exposed, the state cannot be changed through a reader.
+pub struct EventReader<T, B: EventBackend<T> = StdBackend<T>>(WaitEvent<T, B>);
+
+/// Write side of an event from [`WaitEvent::split`]. Clones count as separate setters.
+pub struct EventSetter<T, B: EventBackend<T> = StdBackend<T>>(WaitEvent<T, B>);
+
+// ------------------------------ IMPLEMENTATIONS ------------------------------
+impl<T, B: EventBackend<T>> WaitEvent<T, B> {
+    /// Split this event into a reader and a setter handle, so readers can learn that no setter is left instead of
+    /// blocking forever, like the receiver of a channel. Splitting an event whose setters are all dropped connects it
+    /// again.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use std::thread;
+    /// use sync_wait_object::{WaitEvent, WaitObjectError};
+    ///
+    /// let (reader, mut setter) = WaitEvent::new_init(0).split();
+    ///
+    /// thread::spawn(move || {
+    ///     setter.set_state(1).unwrap();
+    /// });
+    ///
+    /// assert_eq!(*reader.wait(None, |v| *v == 1).unwrap(), 1);
+    /// assert_eq!(reader.wait(None, |v| *v == 2).err(), Some(WaitObjectError::Disconnected));
+    ///
+    /// let ev = WaitEvent::new_init(0);
+    /// let (first, setter) = ev.clone().split();
+    /// drop(setter);
+    /// assert!(first.is_disconnected());
+    ///
+    /// let (second, mut setter) = ev.split();
+    /// assert!(!second.is_disconnected());
+    /// setter.set_state(2).unwrap();
+    /// assert_eq!(*second.wait(None, |v| *v == 2).unwrap(), 2);
+    /// ```
+    pub fn split(self) -> (EventReader<T, B>, EventSetter<T, B>) {
+        // cleared under the lock, like it is flagged by the last setter
+        let state = self.0.backend.lock();
+        if self.0.setters.fetch_add(1, Ordering::SeqCst) == 0 {
+            self.0.disconnected.store(false, Ordering::SeqCst);
+        }
+        drop(state);
+        (EventReader(self.clone()), EventSetter(self))
+    }
+
+    /// Whether all setters from [`split`](Self::split) are dropped.
+    #[inline]
+    pub fn is_disconnected(&self) -> bool {
+        self.0.disconnected.load(Ordering::SeqCst)
+    }
+}
+
+impl<T, B: EventBackend<T>> EventReader<T, B> {
+    /// Current state. Same as [`WaitEvent::value`], but read-only.
+    #[inline]
+    pub fn value(&self) -> Result<impl Deref<Target = T> + '_> { self.0.value() }
+
+    /// Same as [`WaitEvent::check`].
+    #[inline]
+    pub fn check(&self, checker: impl FnOnce(&T) -> bool) -> Result<bool> { self.0.check(checker) }
+
+    /// Same as [`WaitEvent::wait`], but the returned state is read-only.
+    #[inline]
+    pub fn wait(&self, timeout: Option<Duration>, checker: impl FnMut(&T) -> bool) -> Result<impl Deref<Target = T> + '_> {
+        self.0.wait(timeout, checker)
+    }
+
+    /// Same as [`WaitEvent::wait_forever`], but the returned state is read-only.
+    #[inline]
+    pub fn wait_forever(&self, checker: impl FnMut(&T) -> bool) -> Result<impl Deref<Target = T> + '_> {
+        self.0.wait_forever(checker)
+    }
+
+    /// Same as [`WaitEvent::wait_deadline`], but the returned state is read-only.
+    #[inline]
+    pub fn wait_deadline(&self, deadline: Option<Instant>, checker: impl FnMut(&T) -> bool) -> Result<impl Deref<Target = T> + '_> {
+        self.0.wait_deadline(deadline, checker)
+    }
+
+    /// Same as [`WaitEvent::wait_map`].
+    #[inline]
+    pub fn wait_map<R>(&self, timeout: Option<Duration>, checker: impl FnMut(&T) -> bool, f: impl FnOnce(&T) -> R) -> Result<R> {
+        self.0.wait_map(timeout, checker, f)
+    }
+
+    /// Whether all setters of the event are dropped.
+    #[inline]
+    pub fn is_disconnected(&self) -> bool { self.0.is_disconnected() }
+
+    #[inline]
+    pub fn name(&self) -> Option<&str> { self.0.name() }
+}
+
+impl<T: Clone, B: EventBackend<T>> EventReader<T, B> {
+    /// Same as [`WaitEvent::peek`].
+    #[inline]
+    pub fn peek(&self) -> Result<T> { self.0.peek() }
+
+    /// Same as [`WaitEvent::wait_cloned`].
+    #[inline]
+    pub fn wait_cloned(&self, timeout: Option<Duration>, checker: impl FnMut(&T) -> bool) -> Result<T> {
+        self.0.wait_cloned(timeout, checker)
+    }
+}
+
+impl<T, B: EventBackend<T>> Clone for EventReader<T, B> {
+    fn clone(&self) -> Self {
+        Self(self.0.clone())
+    }
+}
+
+impl<T, B: EventBackend<T>> Deref for EventSetter<T, B> {
+    type Target = WaitEvent<T, B>;
+
+    #[inline]
+    fn deref(&self) -> &Self::Target { &self.0 }
+}
+
+impl<T, B: EventBackend<T>> DerefMut for EventSetter<T, B> {
+    #[inline]
+    fn deref_mut(&mut self) -> &mut Self::Target { &mut self.0 }
+}
+
+impl<T, B: EventBackend<T>> Clone for EventSetter<T, B> {
+    fn clone(&self) -> Self {
+        self.0.0.setters.fetch_add(1, Ordering::SeqCst);
+        Self(self.0.clone())
+    }
+}
+
+impl<T, B: EventBackend<T>> Drop for EventSetter<T, B> {
+    fn drop(&mut self) {
+        if self.0.0.setters.fetch_sub(1, Ordering::SeqCst) == 1 {
+            // flagged under the lock, so a waiter cannot miss it between its check and blocking
+            let state = self.0.0.backend.lock();
+            self.0.0.disconnected.store(true, Ordering::SeqCst);
+            self.0.0.backend.notify_all();
+            drop(state);
+        }
+    }
+}
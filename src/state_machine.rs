@@ -0,0 +1,81 @@
+use std::{ sync::{ Arc, MutexGuard }, time::Duration };
+use crate::{ WaitEvent, WaitObjectError, Result };
+
+// ------------------------------ DATA TYPES ----------------------------------
+/// Waitable state machine over `WaitEvent<S>`, where the allowed transitions are declared up front.
+///
+/// # Examples
+///
+/// ```rust
+/// # use std::{thread, time::Duration};
+/// use sync_wait_object::{StateMachine, WaitObjectError};
+///
+/// #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+/// enum Job { Queued, Running, Done, Failed }
+///
+/// let job = StateMachine::new(Job::Queued, [
+///     (Job::Queued, Job::Running),
+///     (Job::Running, Job::Done),
+///     (Job::Running, Job::Failed)
+/// ]);
+/// let mut worker = job.clone();
+///
+/// assert_eq!(worker.transition(Job::Done), Err(WaitObjectError::InvalidTransition));
+///
+/// thread::spawn(move || {
+///     worker.transition(Job::Running).unwrap();
+///     worker.transition(Job::Done).unwrap();
+/// });
+///
+/// let finished = job.wait_any_state(&[Job::Done, Job::Failed], Some(Duration::from_secs(1))).unwrap();
+/// assert_eq!(finished, 0);
+/// ```
+pub struct StateMachine<S> {
+    state: WaitEvent<S>,
+    transitions: Arc<[(S, S)]>
+}
+
+// ------------------------------ IMPLEMENTATIONS ------------------------------
+impl<S: Eq> StateMachine<S> {
+    /// Create a state machine in `initial` state, which only allows the `(from, to)` moves of `transitions`.
+    pub fn new(initial: S, transitions: impl IntoIterator<Item = (S, S)>) -> Self {
+        Self { state: WaitEvent::new_init(initial), transitions: transitions.into_iter().collect() }
+    }
+
+    /// Current state.
+    pub fn state(&self) -> Result<MutexGuard<'_, S>> {
+        self.state.value()
+    }
+
+    /// Whether moving from `from` to `to` is allowed.
+    pub fn can_transition(&self, from: &S, to: &S) -> bool {
+        self.transitions.iter().any(|(f, t)| f == from && t == to)
+    }
+
+    /// Move to state `to`, and wake the waiters. Fails with [`WaitObjectError::InvalidTransition`] if the move from the
+    /// current state is not declared, without waking anyone.
+    pub fn transition(&mut self, to: S) -> Result<()> {
+        let mut current = self.state.lock_for_update()?;
+        if !self.can_transition(&current, &to) { return Err(WaitObjectError::InvalidTransition) }
+        *current = to;
+        self.state.0.notify(&current);
+        Ok(())
+    }
+
+    /// Wait until the machine reaches `state`, or timed-out from `timeout`.
+    pub fn wait_state(&self, state: &S, timeout: Option<Duration>) -> Result<()> {
+        self.state.wait(timeout, |s| s == state).map(|_| ())
+    }
+
+    /// Wait until the machine reaches any of `states`, or timed-out from `timeout`. Returns the index of the reached
+    /// state in `states`.
+    pub fn wait_any_state(&self, states: &[S], timeout: Option<Duration>) -> Result<usize> {
+        self.state.wait_for_any(states, timeout)
+    }
+}
+
+impl<S> Clone for StateMachine<S> {
+    fn clone(&self) -> Self {
+        Self { state: self.state.clone(), transitions: self.transitions.clone() }
+    }
+}
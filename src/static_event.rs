@@ -0,0 +1,82 @@
+use std::{ sync::{ Condvar, Mutex }, time::{ Duration, Instant } };
+use crate::{ Deadline, Result, SignalWaitable, WaitObjectError };
+
+// ------------------------------ DATA TYPES ----------------------------------
+/// Manual-reset event which can be declared as a `static`, without lazy initialization or allocation. Unlike
+/// [`ManualResetEvent`](crate::ManualResetEvent), all operations take `&self`.
+///
+/// # Examples
+///
+/// ```rust
+/// # use std::{thread, time::Duration};
+/// use sync_wait_object::StaticEvent;
+///
+/// static SHUTDOWN: StaticEvent = StaticEvent::new();
+///
+/// thread::spawn(|| SHUTDOWN.set().unwrap());
+///
+/// SHUTDOWN.wait(Duration::from_secs(1)).unwrap();
+/// assert!(SHUTDOWN.is_set().unwrap());
+/// ```
+pub struct StaticEvent {
+    lock: Mutex<bool>,
+    cond: Condvar
+}
+
+// ------------------------------ IMPLEMENTATIONS ------------------------------
+impl StaticEvent {
+    #[inline]
+    pub const fn new() -> Self { Self::new_init(false) }
+
+    #[inline]
+    pub const fn new_init(initial_state: bool) -> Self {
+        Self { lock: Mutex::new(initial_state), cond: Condvar::new() }
+    }
+
+    pub fn is_set(&self) -> Result<bool> {
+        Ok(*self.lock.lock()?)
+    }
+
+    pub fn wait_until_set(&self) -> Result<()> {
+        let state = self.lock.lock()?;
+        drop(self.cond.wait_while(state, |v| !*v)?);
+        Ok(())
+    }
+
+    pub fn wait(&self, timeout: Duration) -> Result<()> {
+        let deadline = Deadline::after(timeout).instant();
+        let mut state = self.lock.lock()?;
+        while !*state {
+            let remaining = match deadline {
+                Some(d) => d.saturating_duration_since(Instant::now()),
+                None => Duration::MAX
+            };
+            if remaining.is_zero() { return Err(WaitObjectError::Timeout) }
+            state = self.cond.wait_timeout(state, remaining)?.0;
+        }
+        Ok(())
+    }
+
+    pub fn set(&self) -> Result<()> {
+        *self.lock.lock()? = true;
+        self.cond.notify_all();
+        Ok(())
+    }
+
+    pub fn reset(&self) -> Result<()> {
+        *self.lock.lock()? = false;
+        Ok(())
+    }
+}
+
+impl SignalWaitable for StaticEvent {
+    #[inline] fn wait_until_set(&self) -> Result<()> { StaticEvent::wait_until_set(self) }
+    #[inline] fn wait(&self, timeout: Duration) -> Result<()> { StaticEvent::wait(self, timeout) }
+    #[inline] fn set(&mut self) -> Result<()> { StaticEvent::set(self) }
+    #[inline] fn reset(&mut self) -> Result<()> { StaticEvent::reset(self) }
+    #[inline] fn is_set(&self) -> Result<bool> { StaticEvent::is_set(self) }
+}
+
+impl Default for StaticEvent {
+    #[inline] fn default() -> Self { Self::new() }
+}
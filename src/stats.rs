@@ -0,0 +1,88 @@
+use std::time::Duration;
+use crate::{ EventBackend, WaitEvent, Result };
+
+const BUCKETS: usize = 64;
+
+// ------------------------------ DATA TYPES ----------------------------------
+/// Wait statistics of an event, shared by all its clones. Durations are kept in a power-of-two histogram (in
+/// microseconds), so percentiles are approximate upper bounds.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WaitStats {
+    /// Number of completed waits, including timed-out ones
+    pub waits: u64,
+
+    /// Number of waits that timed out
+    pub timeouts: u64,
+
+    /// Wakeups where the checker still did not pass, before the timeout
+    pub spurious_wakeups: u64,
+
+    /// Total time spent waiting
+    pub total_wait: Duration,
+
+    histogram: [u64; BUCKETS]
+}
+
+// ------------------------------ IMPLEMENTATIONS ------------------------------
+impl WaitStats {
+    pub(crate) const fn new() -> Self {
+        Self { waits: 0, timeouts: 0, spurious_wakeups: 0, total_wait: Duration::ZERO, histogram: [0; BUCKETS] }
+    }
+
+    pub(crate) fn record(&mut self, waited: Duration, timed_out: bool, spurious_wakeups: u64) {
+        self.waits += 1;
+        self.timeouts += timed_out as u64;
+        self.spurious_wakeups += spurious_wakeups;
+        self.total_wait += waited;
+        let micros = waited.as_micros().min(u64::MAX as u128) as u64;
+        // waits beyond the range of the histogram are counted in its last bucket
+        self.histogram[((u64::BITS - micros.leading_zeros()) as usize).min(BUCKETS - 1)] += 1;
+    }
+
+    /// Average wait duration.
+    pub fn average_wait(&self) -> Duration {
+        if self.waits == 0 { Duration::ZERO }
+        else { Duration::from_nanos((self.total_wait.as_nanos() / self.waits as u128) as u64) }
+    }
+
+    /// Approximate wait duration under which `p` percent of the waits completed.
+    pub fn percentile(&self, p: f64) -> Duration {
+        let target = ((p.clamp(0.0, 100.0) / 100.0) * self.waits as f64).ceil() as u64;
+        let mut seen = 0;
+        for (bucket, count) in self.histogram.iter().enumerate() {
+            seen += count;
+            if seen >= target && *count > 0 {
+                return Duration::from_micros(1u64.checked_shl(bucket as u32).unwrap_or(u64::MAX))
+            }
+        }
+        Duration::ZERO
+    }
+}
+
+impl<T, B: EventBackend<T>> WaitEvent<T, B> {
+    /// Wait statistics of this event, for waits done through [`wait`](Self::wait) and its variants.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use std::time::Duration;
+    /// use sync_wait_object::WaitEvent;
+    ///
+    /// let ev = WaitEvent::new_init(0);
+    /// assert!(ev.wait(Some(Duration::from_millis(5)), |v| *v == 1).is_err());
+    /// drop(ev.wait(None, |v| *v == 0).unwrap());
+    ///
+    /// let stats = ev.stats().unwrap();
+    /// assert_eq!((stats.waits, stats.timeouts), (2, 1));
+    /// assert!(stats.percentile(100.0) >= Duration::from_millis(5));
+    /// ```
+    pub fn stats(&self) -> Result<WaitStats> {
+        Ok(self.0.stats.lock()?.clone())
+    }
+
+    /// Clear the wait statistics of this event.
+    pub fn reset_stats(&self) -> Result<()> {
+        *self.0.stats.lock()? = WaitStats::new();
+        Ok(())
+    }
+}
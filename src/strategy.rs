@@ -0,0 +1,122 @@
+use std::{ thread, time::Duration };
+use crate::{ CancellationToken, Deadline, EventBackend, WaitEvent, WaitObjectError, Result };
+
+// ------------------------------ DATA TYPES ----------------------------------
+/// How a wait passes the time between two checks of the state, see [`WaitEvent::wait_strategy`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum WaitStrategy {
+    /// Park on the condition variable until notified
+    #[default]
+    Park,
+
+    /// Busy-wait for up to the given number of checks, then park, like [`WaitEvent::wait_spin_or_park`]
+    Spin(u32),
+
+    /// Never park, call `thread::yield_now()` between checks, like [`WaitEvent::wait_backoff`] with [`YieldBackoff`]
+    Yield
+}
+
+/// Pause between two checks of a wait which never parks, see [`WaitEvent::wait_backoff`]. Any `FnMut()` is a backoff.
+pub trait Backoff {
+    fn snooze(&mut self);
+}
+
+/// [`Backoff`] yielding the thread on every pause.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct YieldBackoff;
+
+/// [`Backoff`] spinning for exponentially longer on each pause, up to `2^limit` spins, then yielding the thread.
+#[derive(Debug, Clone, Copy)]
+pub struct SpinBackoff {
+    step: u32,
+    limit: u32
+}
+
+// ------------------------------ IMPLEMENTATIONS ------------------------------
+impl<T, B: EventBackend<T>> WaitEvent<T, B> {
+    /// Wait until the `checker` returns true, or timed-out from `timeout`, passing the time between checks as given by
+    /// `strategy`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use std::{thread, time::Duration};
+    /// use sync_wait_object::{WaitEvent, WaitStrategy};
+    ///
+    /// let ev = WaitEvent::new_init(0);
+    /// let mut setter = ev.clone();
+    ///
+    /// thread::spawn(move || setter.set_state(1).unwrap());
+    /// let v = ev.wait_strategy(Some(Duration::from_secs(1)), WaitStrategy::Yield, |v| *v == 1).unwrap();
+    /// assert_eq!(*v, 1);
+    /// ```
+    pub fn wait_strategy(&self, timeout: Option<Duration>, strategy: WaitStrategy, checker: impl FnMut(&T) -> bool) -> Result<B::Guard<'_>> {
+        match strategy {
+            WaitStrategy::Park => self.wait(timeout, checker),
+            WaitStrategy::Spin(max_spins) => self.wait_spin_or_park(timeout, max_spins, checker),
+            WaitStrategy::Yield => self.wait_backoff(timeout, YieldBackoff, checker)
+        }
+    }
+
+    /// Wait until the `checker` returns true, or timed-out from `timeout`, without ever parking the thread. The lock is
+    /// released and `backoff` snoozes between two checks.
+    ///
+    /// # Arguments
+    ///
+    /// * `timeout` - Maximum wait time, `None` for the default timeout (see [`WaitPolicy`](crate::WaitPolicy))
+    /// * `backoff` - Pause between checks
+    /// * `checker` - Checker function, once it returns `true`, the wait ends
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use std::time::Duration;
+    /// use sync_wait_object::{WaitEvent, SpinBackoff, WaitObjectError};
+    ///
+    /// let ev = WaitEvent::new_init(0);
+    /// let r = ev.wait_backoff(Some(Duration::from_millis(5)), SpinBackoff::new(6), |v| *v == 1);
+    /// assert_eq!(r.err(), Some(WaitObjectError::Timeout));
+    ///
+    /// let mut pauses = 0;
+    /// let r = ev.wait_backoff(Some(Duration::from_millis(5)), || pauses += 1, |v| *v == 1);
+    /// assert!(r.is_err() && pauses > 0);
+    /// ```
+    pub fn wait_backoff(&self, timeout: Option<Duration>, mut backoff: impl Backoff, mut checker: impl FnMut(&T) -> bool) -> Result<B::Guard<'_>> {
+        let waiter = Self::create_waiter(Deadline::limit(timeout.or_else(|| self.unset_timeout()))?);
+        loop {
+            let state = self.lock_state()?;
+            if checker(&*state) { return Ok(state) }
+            drop(state);
+            if !waiter() { return Err(WaitObjectError::Timeout) }
+            if CancellationToken::scope_cancelled() { return Err(WaitObjectError::Cancelled) }
+            if self.is_disconnected() { return Err(WaitObjectError::Disconnected) }
+            backoff.snooze();
+        }
+    }
+}
+
+impl Backoff for YieldBackoff {
+    #[inline]
+    fn snooze(&mut self) { thread::yield_now() }
+}
+
+impl SpinBackoff {
+    #[inline]
+    pub fn new(limit: u32) -> Self { Self { step: 0, limit } }
+}
+
+impl Backoff for SpinBackoff {
+    fn snooze(&mut self) {
+        if self.step < self.limit {
+            for _ in 0..1u64 << self.step.min(63) { std::hint::spin_loop() }
+            self.step += 1;
+        } else {
+            thread::yield_now()
+        }
+    }
+}
+
+impl<F: FnMut()> Backoff for F {
+    #[inline]
+    fn snooze(&mut self) { self() }
+}
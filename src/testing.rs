@@ -0,0 +1,216 @@
+//! Fault injection for stress-testing code built on this crate's wait objects. Only available with the `testing`
+//! feature.
+//!
+//! Once enabled, every wait loop iteration may wake up spuriously (the checker is re-evaluated without any
+//! notification) and may be delayed for a short random time. The random sequence is determined by the seed, so a
+//! failing single-threaded scenario replays identically.
+//!
+//! ```rust
+//! # use std::time::Duration;
+//! use sync_wait_object::{WaitEvent, testing};
+//!
+//! testing::enable_spurious_wakeups(42, 0.5, Duration::from_micros(100));
+//!
+//! let ev = WaitEvent::new_init(0);
+//! assert!(ev.wait(Some(Duration::from_millis(20)), |v| *v == 1).is_err());
+//!
+//! testing::disable_spurious_wakeups();
+//! ```
+//!
+//! For more faults on a specific event, see [`WaitEvent::set_chaos`].
+//!
+//! A [`Scheduler`] installed with [`set_scheduler`] observes the points where events lock, block, wake and notify, and
+//! can hold threads at lock points to drive a chosen interleaving.
+
+use std::{ sync::{ Arc, Mutex, RwLock, atomic::{ AtomicU64, Ordering } }, time::Duration };
+use crate::{ EventBackend, WaitEvent, Result };
+
+const GAMMA: u64 = 0x9E37_79B9_7F4A_7C15;
+
+/// Faults injected into one event by [`WaitEvent::set_chaos`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct ChaosConfig {
+    /// Seed of the event's random sequence
+    pub seed: u64,
+
+    /// Maximum random delay before waiters are notified of a change
+    pub max_notify_delay: Duration,
+
+    /// Maximum random delay of each waiter after it wakes up, which shuffles the order waiters get the lock in
+    pub max_wakeup_jitter: Duration,
+
+    /// Probability (0.0 to 1.0) that locking the event fails as if it were poisoned. The infallible API of
+    /// [`NonPoisoning`](crate::NonPoisoning) backends panics on such failures.
+    pub poison_probability: f64
+}
+
+/// Point of an event operation reported to the [`Scheduler`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SchedulePoint {
+    /// About to lock the event state, holding no lock of the event. The scheduler may block the thread here.
+    Lock,
+
+    /// About to block waiting for a notification, under the event lock
+    Block,
+
+    /// Woken up from blocking, under the event lock
+    Wake,
+
+    /// About to notify the waiters of a change, under the event lock
+    Notify
+}
+
+/// Hook observing the blocking and waking points of every event, for deterministic interleaving tests. Only
+/// [`SchedulePoint::Lock`] may block the calling thread; the other points are called under the event lock.
+///
+/// `event` is the identity of the event, see [`WaitEvent::event_id`]. Closures `Fn(SchedulePoint, usize)` are
+/// schedulers.
+pub trait Scheduler: Send + Sync {
+    fn point(&self, point: SchedulePoint, event: usize);
+}
+
+/// Fault injection state of one event.
+pub(crate) struct Chaos {
+    config: ChaosConfig,
+    rng: u64
+}
+
+struct Config {
+    probability: f64,
+    max_delay: Duration
+}
+
+/// Injected faults of one wait loop iteration.
+pub(crate) struct Injection {
+    pub spurious: bool,
+    pub delay: Duration
+}
+
+static CONFIG: Mutex<Option<Config>> = Mutex::new(None);
+static SCHEDULER: RwLock<Option<Arc<dyn Scheduler>>> = RwLock::new(None);
+static RNG: AtomicU64 = AtomicU64::new(0);
+
+/// Start injecting spurious wakeups into all waits, each iteration with the given `probability` (0.0 to 1.0), plus a
+/// random delay up to `max_delay`.
+pub fn enable_spurious_wakeups(seed: u64, probability: f64, max_delay: Duration) {
+    RNG.store(seed, Ordering::SeqCst);
+    *CONFIG.lock().unwrap_or_else(|e| e.into_inner()) = Some(Config { probability: probability.clamp(0.0, 1.0), max_delay });
+}
+
+/// Stop injecting spurious wakeups.
+pub fn disable_spurious_wakeups() {
+    *CONFIG.lock().unwrap_or_else(|e| e.into_inner()) = None;
+}
+
+/// Install a scheduler observing all events, or remove it with `None`.
+///
+/// # Examples
+///
+/// ```rust
+/// use std::sync::{Arc, Mutex};
+/// use sync_wait_object::{WaitEvent, testing::{self, SchedulePoint}};
+///
+/// let mut ev = WaitEvent::new_init(0);
+/// let id = ev.event_id();
+/// let trace = Arc::new(Mutex::new(Vec::new()));
+/// let recorder = trace.clone();
+/// testing::set_scheduler(Some(Arc::new(move |point, event| {
+///     if event == id { recorder.lock().unwrap().push(point) }
+/// })));
+///
+/// ev.set_state(1).unwrap();
+/// ev.wait(None, |v| *v == 1).unwrap();
+/// testing::set_scheduler(None);
+///
+/// assert_eq!(*trace.lock().unwrap(), vec![SchedulePoint::Lock, SchedulePoint::Notify, SchedulePoint::Lock]);
+/// ```
+pub fn set_scheduler(scheduler: Option<Arc<dyn Scheduler>>) {
+    *SCHEDULER.write().unwrap_or_else(|e| e.into_inner()) = scheduler;
+}
+
+pub(crate) fn schedule(point: SchedulePoint, event: usize) {
+    let scheduler = SCHEDULER.read().unwrap_or_else(|e| e.into_inner()).clone();
+    if let Some(scheduler) = scheduler { scheduler.point(point, event) }
+}
+
+/// SplitMix64 output function.
+fn mix(mut z: u64) -> u64 {
+    z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    z ^ (z >> 31)
+}
+
+#[inline]
+fn to_unit(r: u64) -> f64 {
+    (r >> 11) as f64 / (1u64 << 53) as f64
+}
+
+/// Next pseudo-random number in `[0, 1)` of the global seeded sequence.
+fn next_unit() -> f64 {
+    to_unit(mix(RNG.fetch_add(GAMMA, Ordering::SeqCst).wrapping_add(GAMMA)))
+}
+
+pub(crate) fn next_injection() -> Option<Injection> {
+    let config = CONFIG.lock().unwrap_or_else(|e| e.into_inner());
+    let config = config.as_ref()?;
+    let spurious = next_unit() < config.probability;
+    let delay = config.max_delay.mul_f64(next_unit());
+    Some(Injection { spurious, delay })
+}
+
+impl<F: Fn(SchedulePoint, usize) + Send + Sync> Scheduler for F {
+    #[inline]
+    fn point(&self, point: SchedulePoint, event: usize) { self(point, event) }
+}
+
+impl Chaos {
+    fn next_unit(&mut self) -> f64 {
+        self.rng = self.rng.wrapping_add(GAMMA);
+        to_unit(mix(self.rng))
+    }
+
+    pub(crate) fn poison(&mut self) -> bool {
+        self.config.poison_probability > 0.0 && self.next_unit() < self.config.poison_probability
+    }
+
+    pub(crate) fn notify_delay(&mut self) -> Duration {
+        self.config.max_notify_delay.mul_f64(self.next_unit())
+    }
+
+    pub(crate) fn wakeup_injection(&mut self) -> Injection {
+        Injection { spurious: false, delay: self.config.max_wakeup_jitter.mul_f64(self.next_unit()) }
+    }
+}
+
+impl<T, B: EventBackend<T>> WaitEvent<T, B> {
+    /// Inject faults into this event (shared by all its clones), or stop with `None`. Only available with the
+    /// `testing` feature.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use std::time::Duration;
+    /// use sync_wait_object::{WaitEvent, WaitObjectError, testing::ChaosConfig};
+    ///
+    /// let mut ev = WaitEvent::new_init(0);
+    /// ev.set_chaos(Some(ChaosConfig {
+    ///     seed: 7,
+    ///     max_notify_delay: Duration::from_millis(1),
+    ///     max_wakeup_jitter: Duration::from_millis(1),
+    ///     poison_probability: 1.0
+    /// })).unwrap();
+    /// assert_eq!(ev.set_state(1), Err(WaitObjectError::SynchronizationBroken));
+    ///
+    /// ev.set_chaos(None).unwrap();
+    /// assert_eq!(ev.set_state(1), Ok(()));
+    /// ```
+    pub fn set_chaos(&self, config: Option<ChaosConfig>) -> Result<()> {
+        *self.0.chaos.lock()? = config.map(|config| Chaos { rng: config.seed, config });
+        Ok(())
+    }
+
+    /// Identity of this event as reported to a [`Scheduler`], equal for all clones. Only available with the `testing`
+    /// feature.
+    #[inline]
+    pub fn event_id(&self) -> usize { self.id() }
+}
@@ -0,0 +1,126 @@
+use std::{ sync::Arc, thread, time::{ Duration, Instant } };
+use crate::{ timer::TimerCheck, WaitEvent, Result };
+
+// ------------------------------ DATA TYPES ----------------------------------
+/// What [`ThrottledSetter`] does with a notification arriving within the minimum interval.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ThrottleMode {
+    /// Update the value without notifying. Waiters see it on the next notification.
+    Drop,
+
+    /// Update the value, and notify once the interval has passed (from a helper thread shared by the clones of the
+    /// setter).
+    Defer
+}
+
+/// Setter of a [`WaitEvent`] which notifies waiters at most once per `min_interval`, while still updating the value
+/// on every set. Clones share the same interval.
+///
+/// # Examples
+///
+/// ```rust
+/// # use std::time::Duration;
+/// use sync_wait_object::{WaitEvent, ThrottledSetter, ThrottleMode};
+///
+/// let progress = WaitEvent::new_init(0);
+/// let mut setter = ThrottledSetter::new(progress.clone(), Duration::from_millis(20), ThrottleMode::Defer);
+///
+/// for i in 1..=100 {
+///     setter.set_state(i).unwrap();
+/// }
+///
+/// let last = *progress.wait(Some(Duration::from_secs(1)), |v| *v == 100).unwrap();
+/// assert_eq!(last, 100);
+/// ```
+pub struct ThrottledSetter<T> {
+    event: WaitEvent<T>,
+    min_interval: Duration,
+    mode: ThrottleMode,
+    throttle: Arc<ThrottleHandle>
+}
+
+struct Throttle {
+    last_notify: Option<Instant>,
+    /// When the deferred notification is due, if one is pending
+    deferred: Option<Instant>,
+    /// Set once the last clone of the setter drops, which stops the notifier thread
+    closed: bool
+}
+
+/// Throttle state owned by the clones of a setter, which closes it when the last one drops.
+struct ThrottleHandle(WaitEvent<Throttle>);
+
+// ------------------------------ FUNCTIONS -------------------------------------
+/// Send the deferred notifications of `event` when they are due, until the throttle is closed.
+fn notify_deferred<T>(event: WaitEvent<T>, throttle: WaitEvent<Throttle>) {
+    loop {
+        let fired = throttle.wait_timer(None, |t, now| match t.deferred {
+            Some(at) if now < at && !t.closed => TimerCheck::Pending(Some(at)),
+            None if !t.closed => TimerCheck::Pending(None),
+            // a notification still pending when the setter is closed is sent right away
+            _ => TimerCheck::Ready
+        }).map(|mut t| {
+            let due = t.deferred.take().is_some();
+            if due { t.last_notify = Some(Instant::now()) }
+            (due, t.closed)
+        });
+        let Ok((due, closed)) = fired else { break };
+        if due { let _ = event.notify(); }
+        if closed { break }
+    }
+}
+
+// ------------------------------ IMPLEMENTATIONS ------------------------------
+impl<T: Send + 'static> ThrottledSetter<T> {
+    pub fn new(event: WaitEvent<T>, min_interval: Duration, mode: ThrottleMode) -> Self {
+        let throttle = WaitEvent::new_init(Throttle { last_notify: None, deferred: None, closed: false });
+        if mode == ThrottleMode::Defer {
+            let (event, throttle) = (event.clone(), throttle.clone());
+            thread::spawn(move || notify_deferred(event, throttle));
+        }
+        Self { event, min_interval, mode, throttle: Arc::new(ThrottleHandle(throttle)) }
+    }
+
+    /// Change the state, notifying waiters only if `min_interval` has passed since the last notification.
+    pub fn set_state(&mut self, new_state: T) -> Result<()> {
+        self.set_state_func(|_| new_state)
+    }
+
+    /// Change the state by a function's return value, notifying waiters only if `min_interval` has passed since the
+    /// last notification.
+    pub fn set_state_func(&mut self, setter: impl FnOnce(&T) -> T) -> Result<()> {
+        let now = Instant::now();
+        let throttle = &self.throttle.0;
+        let due = throttle.modify_quiet(|t| {
+            let due = match t.last_notify {
+                Some(last) => now - last >= self.min_interval,
+                None => true
+            };
+            if due { t.last_notify = Some(now) }
+            due
+        })?;
+        if due { return self.event.set_state_func(setter) }
+
+        self.event.modify_quiet(|s| *s = setter(s))?;
+        if self.mode == ThrottleMode::Defer {
+            let mut state = throttle.lock_state()?;
+            if state.deferred.is_none() {
+                state.deferred = state.last_notify.map(|last| last + self.min_interval);
+                throttle.commit(state);
+            }
+        }
+        Ok(())
+    }
+}
+
+impl<T> Clone for ThrottledSetter<T> {
+    fn clone(&self) -> Self {
+        Self { event: self.event.clone(), min_interval: self.min_interval, mode: self.mode, throttle: self.throttle.clone() }
+    }
+}
+
+impl Drop for ThrottleHandle {
+    fn drop(&mut self) {
+        let _ = self.0.modify(|t| t.closed = true);
+    }
+}
@@ -0,0 +1,34 @@
+use std::time::{ Duration, Instant };
+use crate::{ CancellationToken, Deadline, EventBackend, WaitEvent, WaitObjectError, Result, ANY_POLL_INTERVAL };
+
+/// Checker outcome of [`WaitEvent::wait_timer`].
+pub(crate) enum TimerCheck {
+    /// The wait ends
+    Ready,
+
+    /// Keep waiting, and re-check at the given instant even if the state is never set
+    Pending(Option<Instant>)
+}
+
+impl<T, B: EventBackend<T>> WaitEvent<T, B> {
+    /// Wait until the `checker` returns [`TimerCheck::Ready`], or timed-out from `timeout`. The checker gets the current
+    /// time and can ask to be re-evaluated at a given instant, for conditions which become true by time passing.
+    pub(crate) fn wait_timer(&self, timeout: Option<Duration>, mut checker: impl FnMut(&T, Instant) -> TimerCheck) -> Result<B::Guard<'_>> {
+        let deadline = Deadline::from_timeout(Deadline::limit(timeout)?).instant();
+        let mut state = self.lock_state()?;
+        loop {
+            let now = Instant::now();
+            let recheck = match checker(&*state, now) {
+                TimerCheck::Ready => return Ok(state),
+                TimerCheck::Pending(recheck) => recheck
+            };
+            if deadline.is_some_and(|d| now >= d) { return Err(WaitObjectError::Timeout) }
+            if CancellationToken::scope_cancelled() { return Err(WaitObjectError::Cancelled) }
+            if self.is_disconnected() { return Err(WaitObjectError::Disconnected) }
+
+            let poll = CancellationToken::in_scope().then(|| now + ANY_POLL_INTERVAL);
+            let wake = [deadline, recheck, poll].into_iter().flatten().min();
+            state = self.block(state, wake)?.0;
+        }
+    }
+}
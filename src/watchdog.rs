@@ -0,0 +1,151 @@
+use std::{ thread, time::{ Duration, Instant } };
+use crate::{ WaitEvent, ManualResetEvent, Result, SignalWaitable, timer::TimerCheck };
+
+// ------------------------------ DATA TYPES ----------------------------------
+/// Watchdog timer which must be [`feed`](Watchdog::feed)-ed at least once per period. Otherwise, a monitor thread
+/// invokes the expiration callback, then sets the [`expired`](Watchdog::expired) event. Feeding again re-arms the
+/// watchdog.
+///
+/// The monitor thread stops when the watchdog drops.
+///
+/// # Examples
+///
+/// ```rust
+/// # use std::{sync::{Arc, atomic::{AtomicBool, Ordering}}, time::Duration};
+/// use sync_wait_object::Watchdog;
+///
+/// let called = Arc::new(AtomicBool::new(false));
+/// let flag = called.clone();
+/// let watchdog = Watchdog::new(Duration::from_millis(20), move || flag.store(true, Ordering::SeqCst));
+///
+/// watchdog.feed().unwrap();
+/// watchdog.wait_expired(Some(Duration::from_secs(1))).unwrap();
+/// assert!(called.load(Ordering::SeqCst));
+/// ```
+pub struct Watchdog {
+    state: WaitEvent<WatchState>,
+    expired: ManualResetEvent
+}
+
+/// Cloneable handle which feeds a [`Watchdog`] from other threads.
+#[derive(Clone)]
+pub struct WatchdogFeeder {
+    state: WaitEvent<WatchState>,
+    expired: ManualResetEvent
+}
+
+struct WatchState {
+    last_feed: Instant,
+    fired: bool,
+    stopped: bool
+}
+
+// ------------------------------ IMPLEMENTATIONS ------------------------------
+impl Watchdog {
+    /// Start a watchdog with the given `period`, which calls `on_expire` (on the monitor thread) each time it expires.
+    pub fn new(period: Duration, on_expire: impl FnMut() + Send + 'static) -> Self {
+        Self::start(period, Some(Box::new(on_expire)))
+    }
+
+    /// Start a watchdog with the given `period`, which only sets the [`expired`](Self::expired) event on expiry.
+    pub fn without_callback(period: Duration) -> Self {
+        Self::start(period, None)
+    }
+
+    fn start(period: Duration, mut on_expire: Option<Box<dyn FnMut() + Send>>) -> Self {
+        let state = WaitEvent::new_init(WatchState { last_feed: Instant::now(), fired: false, stopped: false });
+        let expired = ManualResetEvent::new();
+
+        let monitor = state.clone();
+        let mut expired_signal = expired.clone();
+        thread::spawn(move || loop {
+            let check = monitor.wait_timer(None, |s, now| {
+                if s.stopped { TimerCheck::Ready }
+                else if s.fired { TimerCheck::Pending(None) }
+                else if now - s.last_feed >= period { TimerCheck::Ready }
+                else { TimerCheck::Pending(Some(s.last_feed + period)) }
+            });
+            match check {
+                Ok(mut s) if !s.stopped => s.fired = true,
+                _ => break
+            }
+            // the callback runs first, so waiters of the event observe its effects
+            if let Some(callback) = on_expire.as_mut() { callback() }
+            // the event is only set under the state lock, unless a feed re-armed the watchdog in the meantime
+            let Ok(s) = monitor.value() else { break };
+            if s.fired && !s.stopped { let _ = expired_signal.set(); }
+        });
+        Self { state, expired }
+    }
+
+    /// Reset the watchdog period, and re-arm it if it has expired.
+    ///
+    /// # Examples
+    ///
+    /// A feed while the expiration callback runs keeps the [`expired`](Self::expired) event reset.
+    ///
+    /// ```rust
+    /// # use std::{thread, time::Duration};
+    /// use sync_wait_object::{ManualResetEvent, SignalWaitable, Watchdog};
+    ///
+    /// let (started, finished) = (ManualResetEvent::new(), ManualResetEvent::new());
+    /// let (mut on_start, mut on_finish) = (started.clone(), finished.clone());
+    /// let watchdog = Watchdog::new(Duration::from_millis(200), move || {
+    ///     on_start.set().unwrap();
+    ///     thread::sleep(Duration::from_millis(50));
+    ///     on_finish.set().unwrap();
+    /// });
+    ///
+    /// started.wait(Duration::from_secs(1)).unwrap();
+    /// watchdog.feed().unwrap();
+    /// finished.wait(Duration::from_secs(1)).unwrap();
+    ///
+    /// thread::sleep(Duration::from_millis(20));
+    /// assert!(!watchdog.expired().is_set().unwrap());
+    /// ```
+    #[inline]
+    pub fn feed(&self) -> Result<()> {
+        feed(&self.state, &self.expired)
+    }
+
+    /// Handle to feed this watchdog from other threads.
+    pub fn feeder(&self) -> WatchdogFeeder {
+        WatchdogFeeder { state: self.state.clone(), expired: self.expired.clone() }
+    }
+
+    /// Event which is set while the watchdog is expired.
+    pub fn expired(&self) -> ManualResetEvent {
+        self.expired.clone()
+    }
+
+    /// Wait until the watchdog expires, or timed-out from `timeout`.
+    pub fn wait_expired(&self, timeout: Option<Duration>) -> Result<()> {
+        match timeout {
+            Some(t) => self.expired.wait(t),
+            None => self.expired.wait_until_set()
+        }
+    }
+}
+
+impl WatchdogFeeder {
+    /// Reset the watchdog period, and re-arm it if it has expired.
+    #[inline]
+    pub fn feed(&self) -> Result<()> {
+        feed(&self.state, &self.expired)
+    }
+}
+
+fn feed(state: &WaitEvent<WatchState>, expired: &ManualResetEvent) -> Result<()> {
+    // the event is reset under the state lock, so the monitor cannot set it again for the previous expiry
+    state.modify(|s| {
+        s.last_feed = Instant::now();
+        s.fired = false;
+        expired.clone().reset()
+    })?
+}
+
+impl Drop for Watchdog {
+    fn drop(&mut self) {
+        let _ = self.state.modify(|s| s.stopped = true);
+    }
+}
@@ -5,15 +5,25 @@ use std::{
     ops::{ Deref, DerefMut }
 };
 use windows::Win32::{
-    Foundation::{ HANDLE, CloseHandle, GetLastError, WAIT_OBJECT_0, WAIT_TIMEOUT, WAIT_FAILED, WIN32_ERROR },
+    Foundation::{
+        HANDLE, CloseHandle, GetLastError, WAIT_OBJECT_0, WAIT_TIMEOUT, WAIT_FAILED, WIN32_ERROR,
+        ERROR_ACCESS_DENIED, ERROR_INVALID_HANDLE, ERROR_FILE_NOT_FOUND, ERROR_PATH_NOT_FOUND, ERROR_NOT_FOUND
+    },
     System::Threading::{ CreateEventA, WaitForSingleObject, ResetEvent, SetEvent },
     System::WindowsProgramming::INFINITE
 };
-use crate::{ WaitObjectError, Result, SignalWaitable };
+use crate::{ Deadline, WaitObjectError, Result, SignalWaitable };
+
+mod control;
+mod change;
+mod job;
+pub use control::{ stop_on_console_control, stop_on_service_control };
+pub use change::{ FileChangeNotification, RegistryChangeNotification };
+pub use job::JobWait;
 
 // --------------------------------------- DATA STRUCTURE ---------------------------------------------
 #[derive(Clone)]
-pub struct WaitEvent(HANDLE);
+pub struct WaitEvent(pub(crate) HANDLE);
 
 /// Wrapper of [`WaitEvent`] of type `bool`, which focuses on waiting for `true` without resetting.
 ///
@@ -97,6 +107,28 @@ pub(crate) fn to_result(ret: bool) -> Result<()> {
     else { Err(get_last_error()) }
 }
 
+fn native_wait(handle: HANDLE, timeout: u32) -> Result<()> {
+    let ret = unsafe { WaitForSingleObject(handle, timeout) };
+    match ret {
+        WAIT_OBJECT_0 => Ok(()),
+        WAIT_TIMEOUT => Err(WaitObjectError::Timeout),
+        WAIT_FAILED => Err(get_last_error()),
+        _ => unreachable!()
+    }
+}
+
+/// Wait until `handle` is signaled, or timed-out from `timeout` (see [`Deadline::from_timeout`]).
+pub(crate) fn wait_handle(handle: HANDLE, timeout: Option<Duration>) -> Result<()> {
+    let deadline = Deadline::from_timeout(timeout);
+    loop {
+        match native_wait(handle, deadline.win32_millis()) {
+            // a budget beyond the longest Win32 wait continues with another wait
+            Err(WaitObjectError::Timeout) if !deadline.is_expired() => continue,
+            r => return r
+        }
+    }
+}
+
 pub trait HandleWrapper {
     fn handle(&self) -> HANDLE;
 }
@@ -108,15 +140,35 @@ impl From<WIN32_ERROR> for WaitObjectError {
     }
 }
 
+impl WaitObjectError {
+    /// Underlying Win32 error code, if this is an [`OsError`](WaitObjectError::OsError).
+    pub fn win32_error(&self) -> Option<WIN32_ERROR> {
+        match self {
+            WaitObjectError::OsError(code, _) => u32::try_from(*code).ok().map(WIN32_ERROR),
+            _ => None
+        }
+    }
+
+    #[inline]
+    pub fn is_access_denied(&self) -> bool {
+        self.win32_error() == Some(ERROR_ACCESS_DENIED)
+    }
+
+    #[inline]
+    pub fn is_invalid_handle(&self) -> bool {
+        self.win32_error() == Some(ERROR_INVALID_HANDLE)
+    }
+
+    /// Whether the error is any of `ERROR_FILE_NOT_FOUND`, `ERROR_PATH_NOT_FOUND`, or `ERROR_NOT_FOUND`.
+    pub fn is_not_found(&self) -> bool {
+        matches!(self.win32_error(), Some(e) if e == ERROR_FILE_NOT_FOUND || e == ERROR_PATH_NOT_FOUND || e == ERROR_NOT_FOUND)
+    }
+}
+
 impl WaitEvent {
+    #[inline]
     fn native_wait(&self, timeout: u32) -> Result<()> {
-        let ret = unsafe { WaitForSingleObject(self.0, timeout) };
-        match ret {
-            WAIT_OBJECT_0 => Ok(()),
-            WAIT_TIMEOUT => Err(WaitObjectError::Timeout),
-            WAIT_FAILED => Err(get_last_error()),
-            _ => unreachable!()
-        }
+        native_wait(self.0, timeout)
     }
 }
 
@@ -131,8 +183,9 @@ impl SignalWaitable for WaitEvent {
         self.native_wait(INFINITE)
     }
 
-    #[inline] fn wait(&self, timeout: Duration) -> Result<()> {
-        self.native_wait(timeout.as_millis() as u32)
+    #[inline]
+    fn wait(&self, timeout: Duration) -> Result<()> {
+        wait_handle(self.0, Some(timeout))
     }
 
     fn set(&mut self) -> Result<()> {
@@ -141,6 +194,16 @@ impl SignalWaitable for WaitEvent {
     fn reset(&mut self) -> Result<()> {
         to_result(unsafe { ResetEvent(self.0).as_bool() })
     }
+
+    /// A zero-timeout wait consumes the signal of an auto-reset event, so the event is set again after a successful
+    /// check (a no-op for manual-reset events).
+    fn is_set(&self) -> Result<bool> {
+        match self.native_wait(0) {
+            Ok(()) => to_result(unsafe { SetEvent(self.0).as_bool() }).map(|_| true),
+            Err(WaitObjectError::Timeout) => Ok(false),
+            Err(e) => Err(e)
+        }
+    }
 }
 
 impl Drop for WaitEvent {
@@ -0,0 +1,107 @@
+//! Change notification handles of the file system and the registry as waitables.
+
+use std::{ path::Path, os::windows::ffi::OsStrExt, iter, time::Duration };
+use windows::{
+    core::PCWSTR,
+    Win32::{
+        Foundation::{ HANDLE, NO_ERROR },
+        Storage::FileSystem::{
+            FindFirstChangeNotificationW, FindNextChangeNotification, FindCloseChangeNotification,
+            FindChangeNotificationHandle, FILE_NOTIFY_CHANGE
+        },
+        System::{ Registry::{ RegNotifyChangeKeyValue, HKEY, REG_NOTIFY_FILTER }, Threading::{ CreateEventA, ResetEvent } }
+    }
+};
+use crate::Result;
+use super::{ HandleWrapper, WaitEvent, get_last_error, to_result, wait_handle };
+
+// --------------------------------------- DATA STRUCTURE ---------------------------------------------
+/// Waitable wrapping `FindFirstChangeNotificationW`, signaled when files change under a directory.
+///
+/// [`wait`](Self::wait) re-arms the notification after each change. When the [`handle`](HandleWrapper::handle) is
+/// waited on directly (e.g. with `WaitForMultipleObjects`), call [`rearm`](Self::rearm) after it is signaled.
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// # use std::time::Duration;
+/// use windows::Win32::Storage::FileSystem::FILE_NOTIFY_CHANGE_LAST_WRITE;
+/// use sync_wait_object::windows::FileChangeNotification;
+///
+/// let config_dir = FileChangeNotification::new("C:\\ProgramData\\MyApp", false, FILE_NOTIFY_CHANGE_LAST_WRITE).unwrap();
+/// config_dir.wait(Some(Duration::from_secs(60))).unwrap();
+/// ```
+pub struct FileChangeNotification(FindChangeNotificationHandle);
+
+/// Waitable wrapping `RegNotifyChangeKeyValue`, signaled when a registry key changes.
+///
+/// [`wait`](Self::wait) re-arms the notification after each change. When the [`handle`](HandleWrapper::handle) is
+/// waited on directly, call [`rearm`](Self::rearm) after it is signaled. The key must stay open while this waitable is
+/// in use.
+pub struct RegistryChangeNotification {
+    key: HKEY,
+    watch_subtree: bool,
+    filter: REG_NOTIFY_FILTER,
+    event: WaitEvent
+}
+
+// ---------------------------------------- IMPLEMENTATIONS -------------------------------------------
+impl FileChangeNotification {
+    /// Watch directory `path` (and its subdirectories if `watch_subtree`) for the changes in `filter`.
+    pub fn new(path: impl AsRef<Path>, watch_subtree: bool, filter: FILE_NOTIFY_CHANGE) -> Result<Self> {
+        let path: Vec<u16> = path.as_ref().as_os_str().encode_wide().chain(iter::once(0)).collect();
+        unsafe { FindFirstChangeNotificationW(PCWSTR(path.as_ptr()), watch_subtree, filter) }
+            .map(Self)
+            .map_err(|_| get_last_error())
+    }
+
+    /// Wait until a change happens, or timed-out from `timeout`, then re-arm for the next change.
+    pub fn wait(&self, timeout: Option<Duration>) -> Result<()> {
+        wait_handle(self.handle(), timeout)?;
+        self.rearm()
+    }
+
+    /// Request the next change notification.
+    pub fn rearm(&self) -> Result<()> {
+        to_result(unsafe { FindNextChangeNotification(self.0) }.as_bool())
+    }
+}
+
+impl HandleWrapper for FileChangeNotification {
+    #[inline]
+    fn handle(&self) -> HANDLE { HANDLE(self.0.0) }
+}
+
+impl Drop for FileChangeNotification {
+    fn drop(&mut self) {
+        unsafe { FindCloseChangeNotification(self.0); }
+    }
+}
+
+impl RegistryChangeNotification {
+    /// Watch the open registry `key` (and its subkeys if `watch_subtree`) for the changes in `filter`.
+    pub fn new(key: HKEY, watch_subtree: bool, filter: REG_NOTIFY_FILTER) -> Result<Self> {
+        let event = unsafe { CreateEventA(None, true, false, None) }.map_err(|_| get_last_error())?;
+        let r = Self { key, watch_subtree, filter, event: WaitEvent(event) };
+        r.rearm()?;
+        Ok(r)
+    }
+
+    /// Wait until a change happens, or timed-out from `timeout`, then re-arm for the next change.
+    pub fn wait(&self, timeout: Option<Duration>) -> Result<()> {
+        wait_handle(self.handle(), timeout)?;
+        self.rearm()
+    }
+
+    /// Reset the event and request the next change notification.
+    pub fn rearm(&self) -> Result<()> {
+        to_result(unsafe { ResetEvent(self.event.0) }.as_bool())?;
+        let r = unsafe { RegNotifyChangeKeyValue(self.key, self.watch_subtree, self.filter, self.event.0, true) };
+        if r == NO_ERROR { Ok(()) } else { Err(r.into()) }
+    }
+}
+
+impl HandleWrapper for RegistryChangeNotification {
+    #[inline]
+    fn handle(&self) -> HANDLE { self.event.0 }
+}
@@ -0,0 +1,83 @@
+//! Wiring of Windows stop requests (service control and console control) to events.
+
+use std::{ ffi::c_void, iter, sync::Mutex };
+use windows::{
+    core::PCWSTR,
+    Win32::{
+        Foundation::{ BOOL, NO_ERROR, ERROR_CALL_NOT_IMPLEMENTED },
+        System::{
+            Console::{ SetConsoleCtrlHandler, CTRL_C_EVENT, CTRL_BREAK_EVENT, CTRL_CLOSE_EVENT, CTRL_SHUTDOWN_EVENT },
+            Services::{
+                RegisterServiceCtrlHandlerExW, SERVICE_STATUS_HANDLE,
+                SERVICE_CONTROL_STOP, SERVICE_CONTROL_SHUTDOWN, SERVICE_CONTROL_PRESHUTDOWN, SERVICE_CONTROL_INTERROGATE
+            }
+        }
+    }
+};
+use crate::{ Result, SignalWaitable };
+use super::get_last_error;
+
+type StopEvent = Box<dyn SignalWaitable + Send>;
+
+/// Events set by the console control handler, which is installed with the first one.
+static CONSOLE_EVENTS: Mutex<Vec<StopEvent>> = Mutex::new(Vec::new());
+
+// ---------------------------------------- FUNCTIONS -------------------------------------------------
+/// Register the control handler of service `service_name`, setting `event` on stop and shutdown requests, so a
+/// service can use the same wait-based shutdown flow as a console application. `event` can be a process-local event
+/// (e.g. [`ManualResetEvent`](crate::ManualResetEvent)) or a named one ([`NamedEvent`](crate::ipc::NamedEvent)).
+///
+/// Must be called from the service main function. The returned handle is used to report the service status with
+/// `SetServiceStatus`.
+pub fn stop_on_service_control<E: SignalWaitable + Send + 'static>(service_name: &str, event: E) -> Result<SERVICE_STATUS_HANDLE> {
+    let name: Vec<u16> = service_name.encode_utf16().chain(iter::once(0)).collect();
+    // the handler may be called for the whole life of the service, so its context is never freed
+    let context = Box::into_raw(Box::new(event)) as *const c_void;
+    unsafe { RegisterServiceCtrlHandlerExW(PCWSTR(name.as_ptr()), Some(service_handler::<E>), Some(context)) }
+        .map_err(|_| get_last_error())
+}
+
+/// Set `event` when the console receives Ctrl+C, Ctrl+Break, close or shutdown, instead of terminating the process.
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// use sync_wait_object::{ManualResetEvent, SignalWaitable, windows::stop_on_console_control};
+///
+/// let stop = ManualResetEvent::new();
+/// stop_on_console_control(stop.clone()).unwrap();
+/// stop.wait_until_set().unwrap();
+/// ```
+pub fn stop_on_console_control<E: SignalWaitable + Send + 'static>(event: E) -> Result<()> {
+    let mut events = CONSOLE_EVENTS.lock()?;
+    if events.is_empty() && !unsafe { SetConsoleCtrlHandler(Some(console_handler), true) }.as_bool() {
+        return Err(get_last_error());
+    }
+    events.push(Box::new(event));
+    Ok(())
+}
+
+unsafe extern "system" fn service_handler<E: SignalWaitable>(control: u32, _event_type: u32, _event_data: *mut c_void, context: *mut c_void) -> u32 {
+    match control {
+        SERVICE_CONTROL_STOP | SERVICE_CONTROL_SHUTDOWN | SERVICE_CONTROL_PRESHUTDOWN => {
+            // SAFETY: the context is the leaked event of `stop_on_service_control`, and controls are dispatched one at
+            // a time
+            let event = &mut *(context as *mut E);
+            let _ = event.set();
+            NO_ERROR.0
+        },
+        SERVICE_CONTROL_INTERROGATE => NO_ERROR.0,
+        _ => ERROR_CALL_NOT_IMPLEMENTED.0
+    }
+}
+
+unsafe extern "system" fn console_handler(control: u32) -> BOOL {
+    match control {
+        CTRL_C_EVENT | CTRL_BREAK_EVENT | CTRL_CLOSE_EVENT | CTRL_SHUTDOWN_EVENT => {
+            let mut events = CONSOLE_EVENTS.lock().unwrap_or_else(|e| e.into_inner());
+            for event in events.iter_mut() { let _ = event.set(); }
+            true.into()
+        },
+        _ => false.into()
+    }
+}
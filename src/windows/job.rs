@@ -0,0 +1,132 @@
+//! Job object completion as a waitable.
+
+use std::{ mem, ptr, thread::{ self, JoinHandle }, time::Duration };
+use windows::Win32::{
+    Foundation::{ HANDLE, CloseHandle, INVALID_HANDLE_VALUE },
+    System::{
+        IO::{ CreateIoCompletionPort, GetQueuedCompletionStatus, PostQueuedCompletionStatus, OVERLAPPED },
+        Threading::SetEvent,
+        JobObjects::{
+            AssignProcessToJobObject, CreateJobObjectW, SetInformationJobObject,
+            JOBOBJECT_ASSOCIATE_COMPLETION_PORT, JobObjectAssociateCompletionPortInformation
+        },
+        WindowsProgramming::INFINITE
+    }
+};
+use crate::{ Result, SignalWaitable };
+use super::{ HandleWrapper, ManualResetEvent, get_last_error, to_result };
+
+/// Job message posted when the last process of the job exits (`winnt.h`).
+const JOB_OBJECT_MSG_ACTIVE_PROCESS_ZERO: u32 = 4;
+
+/// Completion key posted by drop to stop the watcher thread. No job handle is null.
+const STOP_KEY: usize = 0;
+
+// --------------------------------------- DATA STRUCTURE ---------------------------------------------
+/// Waitable for "all processes of a job object have exited", for supervising process trees. Its
+/// [`handle`](HandleWrapper::handle) is a manual-reset event which is set at that point, so it can also be mixed into
+/// handle-based multi-waits.
+///
+/// The job is watched through an I/O completion port, so a job given to [`new`](JobWait::new) must not be associated
+/// with another port, and must be watched before its last process exits.
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// # use std::{os::windows::io::AsRawHandle, process::Command, time::Duration};
+/// use windows::Win32::Foundation::HANDLE;
+/// use sync_wait_object::{SignalWaitable, windows::JobWait};
+///
+/// let job = JobWait::create().unwrap();
+/// let child = Command::new("cmd").args(["/C", "exit"]).spawn().unwrap();
+/// job.assign(HANDLE(child.as_raw_handle() as isize)).unwrap();
+///
+/// job.wait(Duration::from_secs(10)).unwrap();
+/// ```
+pub struct JobWait {
+    job: HANDLE,
+    owned: bool,
+    port: HANDLE,
+    done: ManualResetEvent,
+    watcher: Option<JoinHandle<()>>
+}
+
+// ---------------------------------------- IMPLEMENTATIONS -------------------------------------------
+impl JobWait {
+    /// Create a new anonymous job object to watch. Processes are added with [`assign`](Self::assign).
+    pub fn create() -> Result<Self> {
+        let job = unsafe { CreateJobObjectW(None, None) }.map_err(|_| get_last_error())?;
+        Self::watch(job, true).inspect_err(|_| unsafe { CloseHandle(job); })
+    }
+
+    /// Watch the existing job object `job`. The handle stays owned by the caller, and must outlive this waitable.
+    pub fn new(job: HANDLE) -> Result<Self> {
+        Self::watch(job, false)
+    }
+
+    fn watch(job: HANDLE, owned: bool) -> Result<Self> {
+        let port = unsafe { CreateIoCompletionPort(INVALID_HANDLE_VALUE, None, 0, 1) }.map_err(|_| get_last_error())?;
+        let info = JOBOBJECT_ASSOCIATE_COMPLETION_PORT { CompletionKey: job.0 as *mut _, CompletionPort: port };
+        let associated = unsafe {
+            SetInformationJobObject(job, JobObjectAssociateCompletionPortInformation, &info as *const _ as *const _,
+                                    mem::size_of::<JOBOBJECT_ASSOCIATE_COMPLETION_PORT>() as u32)
+        };
+        if let Err(e) = to_result(associated.as_bool()) {
+            unsafe { CloseHandle(port); }
+            return Err(e);
+        }
+
+        let done = ManualResetEvent::new();
+        // a clone of the event would close the shared handle when dropped, so the thread borrows the raw handles, and
+        // drop joins the thread before closing them
+        let (port_raw, job_raw, done_raw) = (port.0, job.0, done.handle().0);
+        let watcher = thread::spawn(move || loop {
+            let (mut message, mut key, mut overlapped) = (0u32, 0usize, ptr::null_mut::<OVERLAPPED>());
+            let ok = unsafe { GetQueuedCompletionStatus(HANDLE(port_raw), &mut message, &mut key, &mut overlapped, INFINITE) };
+            if !ok.as_bool() || key == STOP_KEY { break }
+            if key == job_raw as usize && message == JOB_OBJECT_MSG_ACTIVE_PROCESS_ZERO {
+                unsafe { SetEvent(HANDLE(done_raw)); }
+                break;
+            }
+        });
+        Ok(Self { job, owned, port, done, watcher: Some(watcher) })
+    }
+
+    /// Add the process `process` to the job.
+    pub fn assign(&self, process: HANDLE) -> Result<()> {
+        to_result(unsafe { AssignProcessToJobObject(self.job, process) }.as_bool())
+    }
+
+    /// The watched job object.
+    #[inline]
+    pub fn job(&self) -> HANDLE { self.job }
+}
+
+impl HandleWrapper for JobWait {
+    #[inline]
+    fn handle(&self) -> HANDLE { self.done.handle() }
+}
+
+impl SignalWaitable for JobWait {
+    #[inline] fn wait_until_set(&self) -> Result<()> { self.done.wait_until_set() }
+    #[inline] fn wait(&self, timeout: Duration) -> Result<()> { self.done.wait(timeout) }
+
+    /// Report the job as finished, e.g. to release the waiters on shutdown.
+    #[inline] fn set(&mut self) -> Result<()> { self.done.set() }
+    #[inline] fn reset(&mut self) -> Result<()> { self.done.reset() }
+    #[inline] fn is_set(&self) -> Result<bool> { self.done.is_set() }
+}
+
+impl Drop for JobWait {
+    fn drop(&mut self) {
+        // the watcher may have exited already, on the message it was waiting for
+        let posted = unsafe { PostQueuedCompletionStatus(self.port, 0, STOP_KEY, None) }.as_bool();
+        if let Some(watcher) = self.watcher.take().filter(|_| posted) {
+            let _ = watcher.join();
+        }
+        unsafe {
+            CloseHandle(self.port);
+            if self.owned { CloseHandle(self.job); }
+        }
+    }
+}